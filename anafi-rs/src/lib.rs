@@ -27,8 +27,15 @@ impl Anafi {
         Ok(Self { drone })
     }
 
+    /// Stops the background threads of the `Drone`, see `Drone::disconnect()`
+    pub fn disconnect(&self) {
+        self.drone.disconnect()
+    }
+
     /// - Captain #Ferris 🦀 :Take off... 🛫
-    pub fn take_off(&self) -> Result<(), Error> {
+    ///
+    /// Wait on the returned `AckHandle` to know that the Drone accepted the command.
+    pub fn take_off(&self) -> Result<AckHandle, Error> {
         let feature = Feature::ArDrone3(Some(ArDrone3::Piloting(Piloting::TakeOff)));
 
         let frame = Frame::for_drone(
@@ -41,7 +48,7 @@ impl Anafi {
         self.drone.send_frame(frame)
     }
 
-    pub fn up(&self, sequence_id: u8) -> Result<(), Error> {
+    pub fn up(&self, sequence_id: u8) -> Result<AckHandle, Error> {
         let feature = Feature::ArDrone3(Some(ArDrone3::Piloting(Piloting::PCMD(PCMD {
             flag: true,
             roll: 0,
@@ -57,7 +64,7 @@ impl Anafi {
         self.drone.send_frame(frame)
     }
 
-    pub fn down(&self, sequence_id: u8) -> Result<(), Error> {
+    pub fn down(&self, sequence_id: u8) -> Result<AckHandle, Error> {
         let feature = Feature::ArDrone3(Some(ArDrone3::Piloting(Piloting::PCMD(PCMD {
             flag: true,
             roll: 0,
@@ -73,7 +80,8 @@ impl Anafi {
         self.drone.send_frame(frame)
     }
 
-    pub fn landing(&self) -> Result<(), Error> {
+    /// Wait on the returned `AckHandle` to know that the Drone accepted the command.
+    pub fn landing(&self) -> Result<AckHandle, Error> {
         let feature = Feature::ArDrone3(Some(ArDrone3::Piloting(Piloting::Landing)));
 
         let frame = Frame::for_drone(
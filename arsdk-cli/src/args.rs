@@ -0,0 +1,90 @@
+use anyhow::{anyhow, Context, Result};
+use std::{fmt::Display, str::FromStr};
+
+/// The arguments of a subcommand, options are taken out and the positional arguments are left
+#[derive(Debug)]
+pub struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    pub fn new(args: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            args: args.into_iter().collect(),
+        }
+    }
+
+    /// Takes out the flag, e.g. `--hex`
+    pub fn flag(&mut self, name: &str) -> bool {
+        match self.args.iter().position(|arg| arg == name) {
+            Some(index) => {
+                self.args.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Takes out the option and parses its value, e.g. `--port 43210` or `--port=43210`
+    pub fn value<T>(&mut self, name: &str) -> Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let prefix = format!("{}=", name);
+
+        let value = match self.args.iter().position(|arg| arg == name) {
+            Some(index) if index + 1 < self.args.len() => {
+                self.args.remove(index);
+                self.args.remove(index)
+            }
+            Some(_) => return Err(anyhow!("Missing the value of `{}`", name)),
+            None => match self.args.iter().position(|arg| arg.starts_with(&prefix)) {
+                Some(index) => self.args.remove(index)[prefix.len()..].to_string(),
+                None => return Ok(None),
+            },
+        };
+
+        value
+            .parse()
+            .map(Some)
+            .map_err(|err| anyhow!("{}", err))
+            .with_context(|| format!("Invalid value `{}` of `{}`", value, name))
+    }
+
+    /// The positional arguments, fails for the options that are left
+    pub fn finish(self) -> Result<Vec<String>> {
+        match self.args.iter().find(|arg| arg.starts_with("--")) {
+            Some(option) => Err(anyhow!("Unknown option `{}`", option)),
+            None => Ok(self.args),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(args: &[&str]) -> Args {
+        Args::new(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_args() {
+        let mut args = args(&["1", "--hex", "--d2c-port", "43211", "--c2d-port=54322", "2"]);
+
+        assert!(args.flag("--hex"));
+        assert!(!args.flag("--hex"));
+        assert_eq!(Some(43211), args.value::<u16>("--d2c-port").unwrap());
+        assert_eq!(Some(54322), args.value::<u16>("--c2d-port").unwrap());
+        assert_eq!(None, args.value::<u16>("--file").unwrap());
+        assert_eq!(vec!["1", "2"], args.finish().unwrap());
+    }
+
+    #[test]
+    fn test_invalid_args() {
+        assert!(args(&["--port", "port"]).value::<u16>("--port").is_err());
+        assert!(args(&["--port"]).value::<u16>("--port").is_err());
+        assert!(args(&["--unknown"]).finish().is_err());
+    }
+}
@@ -0,0 +1,298 @@
+use crate::{args::Args, decode::parse_bytes};
+use anyhow::{anyhow, Context, Result};
+use arsdk_rs::{chrono::Utc, record::Recorder, Config, Drone, PARROT_SPHINX_IP};
+use bebop2::{Bebop2, PCMD};
+use jumpingsumo_rs::JumpingSumo;
+use std::{
+    net::IpAddr,
+    path::PathBuf,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+/// How often the piloting command is re-sent
+const PCMD_INTERVAL: Duration = Duration::from_millis(50);
+
+pub const CONNECTION_USAGE: &str = "\
+CONNECTION OPTIONS:
+    --ip <IP>               Address of the Drone [default: 10.202.0.1 - Parrot Sphinx]
+    --init-port <PORT>      TCP port of the Drone for the Handshake [default: 44444]
+    --local-addr <IP>       Local address to bind the sockets to
+    --listen-port <PORT>    Local UDP port for the frames of the Drone [default: 43210]
+    --record <PATH>         Records the session to a capture file";
+
+pub const CONNECT_USAGE: &str = "\
+Connects to the Drone and prints its state and the link quality
+
+USAGE:
+    arsdk connect [--wait <SECONDS>] [CONNECTION OPTIONS]
+
+OPTIONS:
+    --wait <SECONDS>        How long to collect the state for [default: 2]";
+
+pub const TAKEOFF_USAGE: &str = "\
+Takes off an ArDrone3 Drone (Bebop, Bebop 2, Anafi) and waits for the Ack
+
+USAGE:
+    arsdk takeoff [CONNECTION OPTIONS]";
+
+pub const LAND_USAGE: &str = "\
+Lands an ArDrone3 Drone (Bebop, Bebop 2, Anafi) and waits for the Ack
+
+USAGE:
+    arsdk land [CONNECTION OPTIONS]";
+
+pub const EMERGENCY_USAGE: &str = "\
+Cuts the motors of an ArDrone3 Drone (Bebop, Bebop 2, Anafi) and waits for the Ack
+
+USAGE:
+    arsdk emergency [CONNECTION OPTIONS]";
+
+pub const PCMD_USAGE: &str = "\
+Pilots an ArDrone3 Drone (Bebop, Bebop 2, Anafi) for a while and then stops it
+
+USAGE:
+    arsdk pcmd [--roll <%>] [--pitch <%>] [--yaw <%>] [--gaz <%>] [--duration <MS>]
+               [CONNECTION OPTIONS]
+
+OPTIONS:
+    --roll, --pitch, --yaw, --gaz <%>    From -100 to 100 [default: 0]
+    --duration <MS>                      How long to pilot [default: 1000]";
+
+pub const JUMP_USAGE: &str = "\
+Makes a Jumping Sumo jump and waits for the Ack
+
+USAGE:
+    arsdk jump [CONNECTION OPTIONS]";
+
+pub const RAW_USAGE: &str = "\
+Sends the bytes of a Frame as they are, they are not re-sent if the Frame needs an Ack
+
+USAGE:
+    arsdk raw [--hex] <BYTES...> [CONNECTION OPTIONS]
+
+The BYTES are parsed the same way as for `arsdk decode`.";
+
+/// Takes out the connection options
+pub fn config(args: &mut Args) -> Result<Config> {
+    let mut builder = Config::builder(args.value::<IpAddr>("--ip")?.unwrap_or(PARROT_SPHINX_IP));
+
+    if let Some(init_port) = args.value("--init-port")? {
+        builder = builder.init_port(init_port);
+    }
+    if let Some(local_addr) = args.value::<IpAddr>("--local-addr")? {
+        builder = builder.local_addr(local_addr);
+    }
+    if let Some(listen_port) = args.value("--listen-port")? {
+        builder = builder.listen_port(listen_port);
+    }
+    if let Some(path) = args.value::<PathBuf>("--record")? {
+        let recorder =
+            Recorder::create(&path).with_context(|| format!("Creating `{}`", path.display()))?;
+        builder = builder.recorder(recorder);
+    }
+
+    Ok(builder.build()?)
+}
+
+pub fn connect(mut args: Args) -> Result<()> {
+    let config = config(&mut args)?;
+    let wait = Duration::from_secs(args.value("--wait")?.unwrap_or(2));
+    no_arguments(args)?;
+
+    let drone_addr = config.drone_addr;
+    let drone = Drone::connect(config)?;
+    println!("Connected to {}", drone_addr);
+
+    sleep(wait);
+    println!("Link: {:?}", drone.link_state());
+    println!("{:#?}", drone.link_stats());
+    println!("{:#?}", drone.state());
+
+    drone.disconnect();
+    Ok(())
+}
+
+pub fn takeoff(mut args: Args) -> Result<()> {
+    let config = config(&mut args)?;
+    no_arguments(args)?;
+
+    let bebop = Bebop2::connect(config)?;
+
+    bebop.take_off()?.wait()?;
+    println!("Take off acknowledged");
+
+    bebop.disconnect();
+    Ok(())
+}
+
+pub fn land(mut args: Args) -> Result<()> {
+    let config = config(&mut args)?;
+    no_arguments(args)?;
+
+    let bebop = Bebop2::connect(config)?;
+
+    bebop.landing()?.wait()?;
+    println!("Landing acknowledged");
+
+    bebop.disconnect();
+    Ok(())
+}
+
+pub fn emergency(mut args: Args) -> Result<()> {
+    let config = config(&mut args)?;
+    no_arguments(args)?;
+
+    let bebop = Bebop2::connect(config)?;
+
+    bebop.emergency()?.wait()?;
+    println!("Emergency acknowledged");
+
+    bebop.disconnect();
+    Ok(())
+}
+
+pub fn pcmd(mut args: Args) -> Result<()> {
+    let config = config(&mut args)?;
+    let roll = percent(&mut args, "--roll")?;
+    let pitch = percent(&mut args, "--pitch")?;
+    let yaw = percent(&mut args, "--yaw")?;
+    let gaz = percent(&mut args, "--gaz")?;
+    let duration = Duration::from_millis(args.value("--duration")?.unwrap_or(1000));
+    no_arguments(args)?;
+
+    let bebop = Bebop2::connect(config)?;
+    let started = Instant::now();
+    let mut sequence_id = 0_u8;
+
+    while started.elapsed() < duration {
+        bebop.pcmd(PCMD {
+            flag: roll != 0 || pitch != 0,
+            roll,
+            pitch,
+            yaw,
+            gaz,
+            timestamp: Utc::now(),
+            sequence_id,
+        })?;
+
+        sequence_id = sequence_id.wrapping_add(1);
+        sleep(PCMD_INTERVAL);
+    }
+
+    // stop and hover
+    bebop.pcmd(PCMD {
+        flag: false,
+        roll: 0,
+        pitch: 0,
+        yaw: 0,
+        gaz: 0,
+        timestamp: Utc::now(),
+        sequence_id,
+    })?;
+
+    bebop.disconnect();
+    Ok(())
+}
+
+pub fn jump(mut args: Args) -> Result<()> {
+    let config = config(&mut args)?;
+    no_arguments(args)?;
+
+    let sumo = JumpingSumo::connect(config)?;
+
+    sumo.jump()?.wait()?;
+    println!("Jump acknowledged");
+
+    sumo.disconnect();
+    Ok(())
+}
+
+pub fn raw(mut args: Args) -> Result<()> {
+    let config = config(&mut args)?;
+    let hex = args.flag("--hex");
+    let bytes = parse_bytes(&args.finish()?.join(" "), hex)?;
+
+    let drone = Drone::connect(config)?;
+    drone.send_raw_message(&bytes)?;
+
+    // the queued frame is still sent before the Command sender stops
+    drone.disconnect();
+    Ok(())
+}
+
+/// Takes out the option with a value from -100 to 100
+fn percent(args: &mut Args, name: &str) -> Result<i8> {
+    match args.value::<i8>(name)?.unwrap_or(0) {
+        value if (-100..=100).contains(&value) => Ok(value),
+        value => Err(anyhow!(
+            "`{}` should be from -100 to 100, got {}",
+            name,
+            value
+        )),
+    }
+}
+
+fn no_arguments(args: Args) -> Result<()> {
+    match args.finish()?.first() {
+        Some(argument) => Err(anyhow!("Unexpected argument `{}`", argument)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arsdk_rs::{
+        ardrone3::{ArDrone3, Piloting},
+        command::Feature,
+        mock::MockDrone,
+    };
+
+    fn args(args: &[&str]) -> Args {
+        Args::new(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_config() {
+        let mut args = args(&["--ip", "192.168.42.1", "--listen-port=43211", "--wait", "1"]);
+        let config = config(&mut args).expect("Should be a valid config");
+
+        assert_eq!(IpAddr::from([192, 168, 42, 1]), config.drone_addr);
+        assert_eq!(43211, config.listen_port);
+        assert_eq!(None, config.local_addr);
+        // the options of the subcommand are left
+        assert_eq!(Some(1), args.value::<u64>("--wait").unwrap());
+    }
+
+    #[test]
+    fn test_percent() {
+        assert_eq!(-50, percent(&mut args(&["--gaz", "-50"]), "--gaz").unwrap());
+        assert_eq!(0, percent(&mut args(&[]), "--gaz").unwrap());
+        assert!(percent(&mut args(&["--gaz", "101"]), "--gaz").is_err());
+    }
+
+    #[test]
+    fn test_takeoff() {
+        let mock = MockDrone::builder()
+            .start()
+            .expect("Should start the MockDrone");
+        let init_port = mock.init_port().to_string();
+        takeoff(args(&[
+            "--ip",
+            "127.0.0.1",
+            "--init-port",
+            &init_port,
+            // any free port
+            "--listen-port",
+            "0",
+        ]))
+        .expect("Should take off");
+
+        let take_off = Feature::ArDrone3(Some(ArDrone3::Piloting(Piloting::TakeOff)));
+        assert!(mock
+            .received_frames()
+            .iter()
+            .any(|frame| frame.feature.as_ref() == Some(&take_off)));
+    }
+}
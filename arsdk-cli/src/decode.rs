@@ -0,0 +1,171 @@
+use crate::args::Args;
+use anyhow::{anyhow, Context, Result};
+use arsdk_rs::{
+    parse::parse_message_frames,
+    pcap::{PcapReader, Ports},
+    record::{Record, Replayer},
+};
+use std::{
+    io::{self, BufRead, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+pub const USAGE: &str = "\
+Decodes and prints the Frames of datagrams
+
+USAGE:
+    arsdk decode [--hex] [BYTES...]
+    arsdk decode --file <CAPTURE> [--d2c-port <PORT>] [--c2d-port <PORT>]
+
+The BYTES are decimal like the `Bytes: ` log lines, or hex with `--hex` or a `0x` prefix.
+Without BYTES each line of the standard input is decoded as a datagram.
+
+The CAPTURE is either recorded with the `Recorder` or a pcap/pcapng capture,
+from which only the datagrams sent to the `d2c` and `c2d` ports are decoded.";
+
+pub fn run(mut args: Args) -> Result<()> {
+    let hex = args.flag("--hex");
+    let file = args.value::<PathBuf>("--file")?;
+    let defaults = Ports::default();
+    let ports = Ports {
+        d2c_port: args.value("--d2c-port")?.unwrap_or(defaults.d2c_port),
+        c2d_port: args.value("--c2d-port")?.unwrap_or(defaults.c2d_port),
+    };
+    let bytes = args.finish()?;
+
+    match file {
+        Some(path) => decode_capture(&path, ports),
+        None if bytes.is_empty() => {
+            for line in io::stdin().lock().lines() {
+                let line = line?;
+                if !line.trim().is_empty() {
+                    print_frames(&parse_bytes(&line, hex)?);
+                }
+            }
+
+            Ok(())
+        }
+        None => {
+            print_frames(&parse_bytes(&bytes.join(" "), hex)?);
+
+            Ok(())
+        }
+    }
+}
+
+fn decode_capture(path: &Path, ports: Ports) -> Result<()> {
+    let records: Box<dyn Iterator<Item = io::Result<Record>>> = match Replayer::open(path) {
+        Ok(replayer) => Box::new(replayer),
+        // not recorded by us
+        Err(err) if err.kind() == ErrorKind::InvalidData => Box::new(
+            PcapReader::open(path, ports)
+                .with_context(|| format!("Reading `{}`", path.display()))?,
+        ),
+        Err(err) => return Err(err).with_context(|| format!("Reading `{}`", path.display())),
+    };
+
+    for record in records {
+        let record = record?;
+
+        println!(
+            "{} {:?}: {}",
+            record.timestamp,
+            record.direction,
+            print_bytes(&record.data)
+        );
+        print_frames(&record.data);
+    }
+
+    Ok(())
+}
+
+fn print_frames(data: &[u8]) {
+    for frame in parse_message_frames(data) {
+        match frame {
+            Ok(frame) => println!("{:#?}", frame),
+            Err(err) => println!("Error: {:?}", err),
+        }
+    }
+}
+
+fn print_bytes(data: &[u8]) -> String {
+    data.iter()
+        .map(|byte| byte.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parses the bytes of a datagram, e.g. `1 139 0 8 0 0 0 1`, `[1, 139, 0, 8, 0, 0, 0, 1]`,
+/// `0x01 0x8b 0x00 0x08` or with `hex` - `018b000800000001`.
+///
+/// For log lines, only the bytes after the last `: ` are parsed.
+pub fn parse_bytes(text: &str, hex: bool) -> Result<Vec<u8>> {
+    let text = text.rsplit(": ").next().unwrap_or(text);
+    let mut bytes = Vec::new();
+
+    let tokens = text
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '[' || c == ']')
+        .filter(|token| !token.is_empty());
+
+    for token in tokens {
+        let hex_token = token
+            .strip_prefix("0x")
+            .or_else(|| token.strip_prefix("0X"))
+            .or(if hex { Some(token) } else { None });
+
+        match hex_token {
+            Some(hex_token) if hex_token.len() % 2 == 0 => {
+                for index in (0..hex_token.len()).step_by(2) {
+                    let byte = hex_token
+                        .get(index..index + 2)
+                        .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                        .ok_or_else(|| anyhow!("Invalid hex bytes `{}`", token))?;
+                    bytes.push(byte);
+                }
+            }
+            Some(_) => return Err(anyhow!("Odd number of hex digits in `{}`", token)),
+            None => bytes.push(
+                token
+                    .parse()
+                    .with_context(|| format!("Invalid byte `{}`", token))?,
+            ),
+        }
+    }
+
+    if bytes.is_empty() {
+        return Err(anyhow!("No bytes to decode in `{}`", text));
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_bytes() {
+        let ack = vec![1, 139, 0, 8, 0, 0, 0, 1];
+
+        let log_line = "[2020-07-25T18:51:13Z DEBUG arsdk_rs] Bytes: 1 139 0 8 0 0 0 1";
+        assert_eq!(ack, parse_bytes(log_line, false).unwrap());
+        assert_eq!(
+            ack,
+            parse_bytes("[1, 139, 0, 8, 0, 0, 0, 1]", false).unwrap()
+        );
+        assert_eq!(
+            ack,
+            parse_bytes("0x01 0x8b 0x00 0x08 0x00000001", false).unwrap()
+        );
+        assert_eq!(ack, parse_bytes("018b0008 00000001", true).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_bytes() {
+        assert!(parse_bytes("1 256", false).is_err());
+        assert!(parse_bytes("0x1", false).is_err());
+        assert!(parse_bytes("8b", false).is_err());
+        assert!(parse_bytes("0xzz", false).is_err());
+        assert!(parse_bytes("", false).is_err());
+    }
+}
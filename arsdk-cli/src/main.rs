@@ -0,0 +1,73 @@
+//! The `arsdk` command-line tool
+mod args;
+mod control;
+mod decode;
+mod monitor;
+
+use anyhow::{anyhow, Result};
+use args::Args;
+
+const USAGE: &str = "\
+arsdk - command-line tool for the Parrot drones
+
+USAGE:
+    arsdk <COMMAND> [ARGS...]
+
+COMMANDS:
+    decode       Decodes and prints the Frames of datagrams
+    connect      Connects to the Drone and prints its state and the link quality
+    takeoff      Takes off an ArDrone3 Drone
+    land         Lands an ArDrone3 Drone
+    emergency    Cuts the motors of an ArDrone3 Drone
+    pcmd         Pilots an ArDrone3 Drone for a while
+    jump         Makes a Jumping Sumo jump
+    raw          Sends the bytes of a Frame as they are
+    monitor      Prints the decoded frames that the Drone sends
+    help         Prints the usage of a command";
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let command = args.next();
+    let args = Args::new(args);
+
+    match command.as_deref() {
+        Some("decode") => decode::run(args),
+        Some("connect") => control::connect(args),
+        Some("takeoff") => control::takeoff(args),
+        Some("land") => control::land(args),
+        Some("emergency") => control::emergency(args),
+        Some("pcmd") => control::pcmd(args),
+        Some("jump") => control::jump(args),
+        Some("raw") => control::raw(args),
+        Some("monitor") => monitor::run(args),
+        Some("help") | Some("--help") | Some("-h") | None => {
+            let command = args.finish()?.first().cloned().unwrap_or_default();
+
+            match usage(&command) {
+                Some(usage) if command == "decode" => println!("{}", usage),
+                Some(usage) => println!("{}\n\n{}", usage, control::CONNECTION_USAGE),
+                None => println!("{}", USAGE),
+            }
+
+            Ok(())
+        }
+        Some(command) => Err(anyhow!("Unknown command `{}`\n\n{}", command, USAGE)),
+    }
+}
+
+fn usage(command: &str) -> Option<&'static str> {
+    let usage = match command {
+        "decode" => decode::USAGE,
+        "connect" => control::CONNECT_USAGE,
+        "takeoff" => control::TAKEOFF_USAGE,
+        "land" => control::LAND_USAGE,
+        "emergency" => control::EMERGENCY_USAGE,
+        "pcmd" => control::PCMD_USAGE,
+        "jump" => control::JUMP_USAGE,
+        "raw" => control::RAW_USAGE,
+        "monitor" => monitor::USAGE,
+        _ => return None,
+    };
+
+    Some(usage)
+}
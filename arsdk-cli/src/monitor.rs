@@ -0,0 +1,70 @@
+use crate::{args::Args, control::config};
+use anyhow::{anyhow, Result};
+use arsdk_rs::{
+    chrono::Utc,
+    frame::{BufferID, FrameType, Type},
+    subscription::Filter,
+    Drone,
+};
+use std::{
+    sync::mpsc::RecvTimeoutError,
+    time::{Duration, Instant},
+};
+
+pub const USAGE: &str = "\
+Connects to the Drone and prints the decoded frames that it sends
+
+USAGE:
+    arsdk monitor [--all] [--feature <ID> [--class <ID>]] [--duration <SECONDS>]
+                  [CONNECTION OPTIONS]
+
+OPTIONS:
+    --all                   Prints also the PINGs, PONGs and Acks
+    --feature <ID>          Prints only the frames of the Feature, e.g. 1 for ArDrone3
+    --class <ID>            Prints only the frames of the class of the Feature
+    --duration <SECONDS>    How long to monitor for [default: until stopped]";
+
+pub fn run(mut args: Args) -> Result<()> {
+    let config = config(&mut args)?;
+    let all = args.flag("--all");
+    let filter = match (args.value("--feature")?, args.value("--class")?) {
+        (Some(feature), Some(class)) => Filter::Class { feature, class },
+        (Some(feature), None) => Filter::Feature(feature),
+        (None, Some(_)) => return Err(anyhow!("`--class` needs a `--feature`")),
+        (None, None) => Filter::All,
+    };
+    let duration = args.value("--duration")?.map(Duration::from_secs);
+    if let Some(argument) = args.finish()?.first() {
+        return Err(anyhow!("Unexpected argument `{}`", argument));
+    }
+
+    let drone = Drone::connect(config)?;
+    let subscription = drone.subscribe(filter);
+    let started = Instant::now();
+
+    while duration.map_or(true, |duration| started.elapsed() < duration) {
+        let frame = match subscription.recv_timeout(Duration::from_millis(100)) {
+            Ok(frame) => frame,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+
+        if all || !is_link_frame(&frame) {
+            println!("{} {:?}", Utc::now(), frame);
+        }
+    }
+
+    drone.disconnect();
+    Ok(())
+}
+
+/// PINGs, PONGs and Acks are only for keeping the link
+fn is_link_frame(frame: &FrameType) -> bool {
+    match frame {
+        FrameType::Known(frame) => {
+            frame.frame_type == Type::Ack
+                || [BufferID::PING, BufferID::PONG].contains(&frame.buffer_id)
+        }
+        FrameType::Unknown(_) => false,
+    }
+}
@@ -0,0 +1,582 @@
+//! Generates the Rust code of the features.
+//!
+//! Each feature becomes a module with a `Class` enum and its shared enums, each class a module with
+//! a `Command` enum, a struct for each command with arguments and an enum for each `enum` argument.
+//! All of them implement the `scroll` `TryFromCtx` and `TryIntoCtx` with `crate::frame::Error`,
+//! so the generated code is meant to be included in `arsdk-rs`.
+use crate::model::{Arg, ArgType, Class, Command, EnumValue, Feature, IntType};
+
+const ERROR: &str = "crate::frame::Error";
+const RESULT: &str = "std::result::Result";
+
+/// Keywords which can't be used as field names
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+/// Generates the code of all the features.
+///
+/// The `Class` of each feature is meant to be held by a variant of `arsdk_rs::command::Feature`.
+pub fn generate(features: &[Feature]) -> String {
+    let mut code = Code::default();
+
+    code.line("// Generated by arsdk-codegen from the arsdk-xml definitions, do not edit.");
+
+    for feature in features.iter().filter(|feature| has_commands(feature)) {
+        generate_feature(&mut code, feature);
+    }
+
+    code.source
+}
+
+fn has_commands(feature: &Feature) -> bool {
+    feature
+        .classes
+        .iter()
+        .any(|class| !class.commands.is_empty())
+}
+
+fn generate_feature(code: &mut Code, feature: &Feature) {
+    let name = module(&feature.name);
+    let classes = feature
+        .classes
+        .iter()
+        .filter(|class| !class.commands.is_empty())
+        .collect::<Vec<_>>();
+
+    code.line("");
+    code.doc(&feature.doc);
+    code.open(&format!("pub mod {} {{", name));
+    code.line("use scroll::{Pread as _, Pwrite as _};");
+    code.line("");
+    code.line(&format!("pub const FEATURE_ID: u8 = {};", feature.id));
+    code.line("");
+    code.line("#[derive(Debug, Clone, PartialEq)]");
+    code.open("pub enum Class {");
+    for class in &classes {
+        code.doc(&class.doc);
+        code.line(&format!(
+            "{}({}::Command),",
+            upper_camel(&class.name),
+            module(&class.name)
+        ));
+    }
+    code.close("}");
+
+    let variants = classes
+        .iter()
+        .map(|class| {
+            (
+                upper_camel(&class.name),
+                format!("{}::CLASS_ID", module(&class.name)),
+            )
+        })
+        .collect::<Vec<_>>();
+    enum_codec(code, "Class", "u8", &variants, &format!("{}::Class", name));
+
+    // outside of the class modules, so they don't collide with the commands
+    for enum_type in &feature.enums {
+        generate_enum(
+            code,
+            &name,
+            &upper_camel(&enum_type.name),
+            &enum_type.doc,
+            &enum_type.values,
+        );
+    }
+
+    for class in classes {
+        generate_class(code, &name, class);
+    }
+
+    code.close("}");
+}
+
+fn generate_class(code: &mut Code, feature: &str, class: &Class) {
+    let name = module(&class.name);
+    let path = format!("{}::{}", feature, name);
+
+    code.line("");
+    code.doc(&class.doc);
+    code.line("#[allow(clippy::module_inception)]");
+    code.open(&format!("pub mod {} {{", name));
+    code.line("use scroll::{Pread as _, Pwrite as _};");
+    code.line("");
+    code.line(&format!("pub const CLASS_ID: u8 = {};", class.id));
+    code.line("");
+    code.line("#[allow(clippy::enum_variant_names, clippy::large_enum_variant)]");
+    code.line("#[derive(Debug, Clone, PartialEq)]");
+    code.open("pub enum Command {");
+    for command in &class.commands {
+        command_doc(code, command);
+        match command.args.is_empty() {
+            true => code.line(&format!("{},", upper_camel(&command.name))),
+            false => code.line(&format!("{0}({0}),", upper_camel(&command.name))),
+        }
+    }
+    code.close("}");
+
+    let variants = class
+        .commands
+        .iter()
+        .map(|command| (upper_camel(&command.name), command.id.to_string()))
+        .collect::<Vec<_>>();
+    let unit_variants = class
+        .commands
+        .iter()
+        .map(|command| command.args.is_empty())
+        .collect::<Vec<_>>();
+    enum_codec_with_units(
+        code,
+        "Command",
+        "u16",
+        &variants,
+        &unit_variants,
+        &format!("{}::Command", path),
+    );
+
+    for command in &class.commands {
+        if !command.args.is_empty() {
+            generate_command(code, &path, command);
+        }
+    }
+
+    code.close("}");
+}
+
+fn command_doc(code: &mut Code, command: &Command) {
+    code.doc(&command.doc);
+    if command.deprecated {
+        if !command.doc.is_empty() {
+            code.line("///");
+        }
+        code.line("/// Deprecated");
+    }
+}
+
+fn generate_command(code: &mut Code, path: &str, command: &Command) {
+    let name = upper_camel(&command.name);
+
+    code.line("");
+    command_doc(code, command);
+    code.line("#[derive(Debug, Clone, PartialEq)]");
+    code.open(&format!("pub struct {} {{", name));
+    for arg in &command.args {
+        code.doc(&arg.doc);
+        code.line(&format!(
+            "pub {}: {},",
+            field(&arg.name),
+            rust_type(command, arg)
+        ));
+    }
+    code.close("}");
+
+    // strings are read and written without the `Endian`
+    let ctx = match command.args.iter().all(|arg| arg.ty == ArgType::String) {
+        true => "_ctx",
+        false => "ctx",
+    };
+
+    code.line("");
+    code.open(&format!(
+        "impl<'a> scroll::ctx::TryFromCtx<'a, scroll::Endian> for {} {{",
+        name
+    ));
+    code.line(&format!("type Error = {};", ERROR));
+    code.line("");
+    code.open(&format!(
+        "fn try_from_ctx(src: &'a [u8], {}: scroll::Endian) -> {}<(Self, usize), {}> {{",
+        ctx, RESULT, ERROR
+    ));
+    code.line("let mut offset = 0;");
+    code.open("let command = Self {");
+    for arg in &command.args {
+        let read = match &arg.ty {
+            ArgType::String => "crate::parse::read_string(src, &mut offset)?".to_string(),
+            ArgType::Enum(_) | ArgType::NamedEnum(_) => {
+                "std::convert::TryFrom::try_from(src.gread_with::<u32>(&mut offset, ctx)?)?"
+                    .to_string()
+            }
+            _ => format!(
+                "src.gread_with::<{}>(&mut offset, ctx)?",
+                rust_type(command, arg)
+            ),
+        };
+        code.line(&format!("{}: {},", field(&arg.name), read));
+    }
+    code.close("};");
+    code.line("");
+    code.line("Ok((command, offset))");
+    code.close("}");
+    code.close("}");
+
+    code.line("");
+    code.open(&format!(
+        "impl scroll::ctx::TryIntoCtx<scroll::Endian> for {} {{",
+        name
+    ));
+    code.line(&format!("type Error = {};", ERROR));
+    code.line("");
+    code.open(&format!(
+        "fn try_into_ctx(self, this: &mut [u8], {}: scroll::Endian) -> {}<usize, {}> {{",
+        ctx, RESULT, ERROR
+    ));
+    code.line("let mut offset = 0;");
+    for arg in &command.args {
+        let field = field(&arg.name);
+        match &arg.ty {
+            ArgType::String => code.line(&format!(
+                "crate::parse::write_string(this, &mut offset, &self.{})?;",
+                field
+            )),
+            ArgType::Enum(_) | ArgType::NamedEnum(_) => code.line(&format!(
+                "this.gwrite_with(self.{} as u32, &mut offset, ctx)?;",
+                field
+            )),
+            _ => code.line(&format!(
+                "this.gwrite_with(self.{}, &mut offset, ctx)?;",
+                field
+            )),
+        }
+    }
+    code.line("");
+    code.line("Ok(offset)");
+    code.close("}");
+    code.close("}");
+
+    for arg in &command.args {
+        if let ArgType::Enum(values) = &arg.ty {
+            generate_enum(code, path, &enum_name(command, arg), &arg.doc, values);
+        }
+    }
+}
+
+fn generate_enum(code: &mut Code, path: &str, name: &str, doc: &str, values: &[EnumValue]) {
+    code.line("");
+    code.doc(doc);
+    code.line("#[derive(Debug, Clone, Copy, PartialEq, Eq)]");
+    code.open(&format!("pub enum {} {{", name));
+    for value in values {
+        code.doc(&value.doc);
+        code.line(&format!("{} = {},", upper_camel(&value.name), value.value));
+    }
+    code.close("}");
+
+    code.line("");
+    code.open(&format!("impl std::convert::TryFrom<u32> for {} {{", name));
+    code.line(&format!("type Error = {};", ERROR));
+    code.line("");
+    code.open(&format!(
+        "fn try_from(value: u32) -> {}<Self, {}> {{",
+        RESULT, ERROR
+    ));
+    code.open("match value {");
+    for value in values {
+        code.line(&format!(
+            "{} => Ok(Self::{}),",
+            value.value,
+            upper_camel(&value.name)
+        ));
+    }
+    code.line(&format!(
+        "value => Err({}::OutOfBound {{ value: value.into(), param: \"{}::{}\".to_string() }}),",
+        ERROR, path, name
+    ));
+    code.close("}");
+    code.close("}");
+    code.close("}");
+}
+
+/// The `id()` and the codec of an enum of which each variant holds the next level
+fn enum_codec(code: &mut Code, name: &str, id: &str, variants: &[(String, String)], path: &str) {
+    let units = vec![false; variants.len()];
+
+    enum_codec_with_units(code, name, id, variants, &units, path)
+}
+
+/// The `id()` and the codec of an enum, some of its variants may not hold anything
+fn enum_codec_with_units(
+    code: &mut Code,
+    name: &str,
+    id: &str,
+    variants: &[(String, String)],
+    units: &[bool],
+    path: &str,
+) {
+    let pattern = |variant: &str, unit: bool| match unit {
+        true => format!("Self::{}", variant),
+        false => format!("Self::{}(_)", variant),
+    };
+
+    code.line("");
+    code.open(&format!("impl {} {{", name));
+    code.open(&format!("pub fn id(&self) -> {} {{", id));
+    code.open("match self {");
+    for ((variant, variant_id), unit) in variants.iter().zip(units) {
+        code.line(&format!("{} => {},", pattern(variant, *unit), variant_id));
+    }
+    code.close("}");
+    code.close("}");
+    code.close("}");
+
+    code.line("");
+    code.open(&format!(
+        "impl<'a> scroll::ctx::TryFromCtx<'a, scroll::Endian> for {} {{",
+        name
+    ));
+    code.line(&format!("type Error = {};", ERROR));
+    code.line("");
+    code.open(&format!(
+        "fn try_from_ctx(src: &'a [u8], ctx: scroll::Endian) -> {}<(Self, usize), {}> {{",
+        RESULT, ERROR
+    ));
+    code.line("let mut offset = 0;");
+    code.open(&format!(
+        "let value = match src.gread_with::<{}>(&mut offset, ctx)? {{",
+        id
+    ));
+    for ((variant, variant_id), unit) in variants.iter().zip(units) {
+        match unit {
+            true => code.line(&format!("id if id == {} => Self::{},", variant_id, variant)),
+            false => code.line(&format!(
+                "id if id == {} => Self::{}(src.gread_with(&mut offset, ctx)?),",
+                variant_id, variant
+            )),
+        }
+    }
+    code.line(&format!(
+        "value => return Err({}::OutOfBound {{ value: value.into(), param: \"{}\".to_string() }}),",
+        ERROR, path
+    ));
+    code.close("};");
+    code.line("");
+    code.line("Ok((value, offset))");
+    code.close("}");
+    code.close("}");
+
+    code.line("");
+    code.open(&format!(
+        "impl scroll::ctx::TryIntoCtx<scroll::Endian> for {} {{",
+        name
+    ));
+    code.line(&format!("type Error = {};", ERROR));
+    code.line("");
+    code.open(&format!(
+        "fn try_into_ctx(self, this: &mut [u8], ctx: scroll::Endian) -> {}<usize, {}> {{",
+        RESULT, ERROR
+    ));
+    code.line("let mut offset = 0;");
+    code.line(&format!(
+        "this.gwrite_with::<{}>(self.id(), &mut offset, ctx)?;",
+        id
+    ));
+    code.line("");
+    code.open("match self {");
+    for ((variant, _), unit) in variants.iter().zip(units) {
+        match unit {
+            true => code.line(&format!("Self::{} => {{}}", variant)),
+            false => code.line(&format!(
+                "Self::{}(value) => {{ this.gwrite_with(value, &mut offset, ctx)?; }}",
+                variant
+            )),
+        }
+    }
+    code.close("}");
+    code.line("");
+    code.line("Ok(offset)");
+    code.close("}");
+    code.close("}");
+}
+
+fn rust_type(command: &Command, arg: &Arg) -> String {
+    match &arg.ty {
+        ArgType::Int(int_type) | ArgType::Bitfield(int_type) => int_type_name(*int_type).into(),
+        ArgType::Float => "f32".into(),
+        ArgType::Double => "f64".into(),
+        ArgType::String => "std::string::String".into(),
+        ArgType::Enum(_) => enum_name(command, arg),
+        ArgType::NamedEnum(name) => format!("super::{}", upper_camel(name)),
+    }
+}
+
+fn int_type_name(int_type: IntType) -> &'static str {
+    match int_type {
+        IntType::U8 => "u8",
+        IntType::I8 => "i8",
+        IntType::U16 => "u16",
+        IntType::I16 => "i16",
+        IntType::U32 => "u32",
+        IntType::I32 => "i32",
+        IntType::U64 => "u64",
+        IntType::I64 => "i64",
+    }
+}
+
+/// e.g. `FlyingStateChangedState` for the `state` of `FlyingStateChanged`
+fn enum_name(command: &Command, arg: &Arg) -> String {
+    format!("{}{}", upper_camel(&command.name), upper_camel(&arg.name))
+}
+
+/// e.g. `Piloting` for `Piloting`, `MotorRamping` for `motor_ramping`, `ToTarget` for `TO_TARGET`
+pub(crate) fn upper_camel(name: &str) -> String {
+    let camel = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .flat_map(|part| {
+            // the acronyms and the upper case values are capitalized as words
+            let upper_case = !part.chars().any(|c| c.is_ascii_lowercase());
+            let mut chars = part.chars();
+
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars.map(move |c| match upper_case {
+                    true => c.to_ascii_lowercase(),
+                    false => c,
+                }))
+        })
+        .collect::<String>();
+
+    match camel.chars().next() {
+        Some(first) if first.is_ascii_digit() => format!("V{}", camel),
+        _ => camel,
+    }
+}
+
+/// e.g. `timestamp_and_seq_num` for `timestampAndSeqNum`, `gps_state` for `GPSState`
+pub(crate) fn snake(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut snake = String::with_capacity(name.len() + 4);
+
+    for (index, c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !snake.ends_with('_') {
+                snake.push('_');
+            }
+            continue;
+        }
+
+        if c.is_ascii_uppercase() && index > 0 {
+            let previous = chars[index - 1];
+            let next = chars.get(index + 1);
+            let word_start = previous.is_ascii_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_ascii_uppercase()
+                    && matches!(next, Some(next) if next.is_ascii_lowercase()));
+
+            if word_start && !snake.ends_with('_') {
+                snake.push('_');
+            }
+        }
+
+        snake.push(c.to_ascii_lowercase());
+    }
+
+    snake.trim_matches('_').to_string()
+}
+
+fn module(name: &str) -> String {
+    field(name)
+}
+
+fn field(name: &str) -> String {
+    let snake = snake(name);
+
+    if KEYWORDS.contains(&snake.as_str()) {
+        format!("r#{}", snake)
+    } else if snake.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", snake)
+    } else {
+        snake
+    }
+}
+
+/// The generated source with indentation
+#[derive(Default)]
+struct Code {
+    source: String,
+    indent: usize,
+}
+
+impl Code {
+    fn line(&mut self, line: &str) {
+        if !line.is_empty() {
+            self.source.push_str(&"    ".repeat(self.indent));
+            self.source.push_str(line);
+        }
+        self.source.push('\n');
+    }
+
+    /// Adds the line and indents the next ones
+    fn open(&mut self, line: &str) {
+        self.line(line);
+        self.indent += 1;
+    }
+
+    /// Unindents and adds the line
+    fn close(&mut self, line: &str) {
+        self.indent -= 1;
+        self.line(line);
+    }
+
+    fn doc(&mut self, doc: &str) {
+        for line in doc.lines() {
+            self.line(format!("/// {}", line.trim()).trim_end());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        assert_eq!("FlyingStateChanged", upper_camel("FlyingStateChanged"));
+        assert_eq!("MotorRamping", upper_camel("motor_ramping"));
+        assert_eq!("Pcmd", upper_camel("PCMD"));
+        assert_eq!("GPSState", upper_camel("GPSState"));
+        assert_eq!("ToTarget", upper_camel("TO_TARGET"));
+        assert_eq!("V1080p", upper_camel("1080p"));
+
+        assert_eq!("timestamp_and_seq_num", snake("timestampAndSeqNum"));
+        assert_eq!("speed_x", snake("speedX"));
+        assert_eq!("gps_state", snake("GPSState"));
+        assert_eq!("pcmd", snake("PCMD"));
+        assert_eq!("piloted_poi", snake("PilotedPOI"));
+        assert_eq!("r#type", field("type"));
+    }
+
+    #[test]
+    fn test_generate() {
+        let feature = Feature::parse(
+            r#"<feature id="3" name="jpsumo">
+                <class name="Piloting" id="0">
+                    <cmd name="Posture" id="1">
+                        <arg name="type" type="enum"><enum name="standard"/></arg>
+                    </cmd>
+                    <cmd name="Stop" id="2"/>
+                </class>
+                <class name="Empty" id="1"/>
+            </feature>"#,
+        )
+        .expect("Should parse");
+
+        let code = generate(&[feature]);
+
+        assert!(code.contains("pub mod jpsumo {"));
+        assert!(code.contains("Piloting(piloting::Command),"));
+        // the features are held by `arsdk_rs::command::Feature`
+        assert!(!code.contains("pub enum Feature"));
+        assert!(code.contains("Posture(Posture),"));
+        assert!(code.contains("pub r#type: PostureType,"));
+        assert!(code.contains("Standard = 0,"));
+        assert!(code.contains("id if id == 2 => Self::Stop,"));
+        // classes without commands are skipped
+        assert!(!code.contains("mod empty"));
+    }
+}
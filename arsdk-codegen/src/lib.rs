@@ -0,0 +1,87 @@
+//! Generates the commands of `arsdk-rs` from Parrot's [arsdk-xml] definitions.
+//!
+//! Used in the `build.rs` of `arsdk-rs`:
+//!
+//! ```ignore
+//! let out = Path::new(&env::var("OUT_DIR")?).join("generated.rs");
+//! arsdk_codegen::generate_dir("xml", out)?;
+//! ```
+//!
+//! [arsdk-xml]: https://github.com/Parrot-Developers/arsdk-xml
+pub mod generate;
+pub mod model;
+pub mod xml;
+
+pub use generate::generate;
+pub use model::Feature;
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Reading or writing {path}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        error: io::Error,
+    },
+    #[error("XML line {line}: {message}")]
+    Xml { line: usize, message: String },
+    #[error("Invalid definition: {0}")]
+    Definition(String),
+    #[error("{path}: {error}")]
+    File {
+        path: PathBuf,
+        #[source]
+        error: Box<Error>,
+    },
+}
+
+/// Parses every `.xml` file in the directory, sorted by the feature id
+pub fn parse_dir(xml_dir: impl AsRef<Path>) -> Result<Vec<Feature>, Error> {
+    let xml_dir = xml_dir.as_ref();
+    let io_error = |error| Error::Io {
+        path: xml_dir.to_path_buf(),
+        error,
+    };
+
+    let mut paths = fs::read_dir(xml_dir)
+        .map_err(io_error)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(io_error)?;
+    paths.retain(|path| path.extension() == Some("xml".as_ref()));
+    paths.sort();
+
+    let mut features = paths
+        .into_iter()
+        .map(|path| {
+            let source = fs::read_to_string(&path).map_err(|error| Error::Io {
+                path: path.clone(),
+                error,
+            })?;
+
+            Feature::parse(&source).map_err(|error| Error::File {
+                path,
+                error: Box::new(error),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    features.sort_by_key(|feature| feature.id);
+
+    Ok(features)
+}
+
+/// Generates the code of the definitions in `xml_dir` and writes it to `out`
+pub fn generate_dir(xml_dir: impl AsRef<Path>, out: impl AsRef<Path>) -> Result<(), Error> {
+    let code = generate(&parse_dir(xml_dir)?);
+
+    fs::write(out.as_ref(), code).map_err(|error| Error::Io {
+        path: out.as_ref().to_path_buf(),
+        error,
+    })
+}
@@ -0,0 +1,471 @@
+//! The features, classes, commands and their arguments of the arsdk-xml definitions.
+//!
+//! The older definitions group the commands in classes:
+//!
+//! ```xml
+//! <feature id="1" name="ardrone3">
+//!     <class name="Piloting" id="0">
+//!         <cmd name="PCMD" id="2" buffer="NON_ACK">
+//!             <comment title="Move the drone"/>
+//!             <arg name="flag" type="u8">...</arg>
+//!             ...
+//!         </cmd>
+//!     </class>
+//! </feature>
+//! ```
+//!
+//! The newer ones (e.g. the Anafi features) have the commands and the events in `<msgs>`,
+//! with the enums of the feature shared between them. They are sent with class `0`,
+//! so they become the single `Msgs` class:
+//!
+//! ```xml
+//! <feature id="148" name="gimbal">
+//!     <enums>
+//!         <enum name="control_mode">
+//!             <value name="position">...</value>
+//!             ...
+//!         </enum>
+//!     </enums>
+//!     <msgs>
+//!         <cmd name="set_target" id="7">
+//!             <arg name="control_mode" type="enum:control_mode">...</arg>
+//!             ...
+//!         </cmd>
+//!         <evt name="attitude" id="6">...</evt>
+//!     </msgs>
+//! </feature>
+//! ```
+use crate::{
+    xml::{self, Element},
+    Error,
+};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Feature {
+    pub id: u8,
+    pub name: String,
+    pub doc: String,
+    pub classes: Vec<Class>,
+    /// The enums shared between the commands, only in the `<msgs>` definitions
+    pub enums: Vec<Enum>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Class {
+    pub id: u8,
+    pub name: String,
+    pub doc: String,
+    pub commands: Vec<Command>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Command {
+    pub id: u16,
+    pub name: String,
+    pub doc: String,
+    pub deprecated: bool,
+    pub args: Vec<Arg>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arg {
+    pub name: String,
+    pub doc: String,
+    pub ty: ArgType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntType {
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgType {
+    Int(IntType),
+    Float,
+    Double,
+    /// Null terminated
+    String,
+    /// Sent as `u32`
+    Enum(Vec<EnumValue>),
+    /// One of the `Feature::enums` by its name, sent as `u32`
+    NamedEnum(String),
+    /// Bit flags of an enum, sent as the integer
+    Bitfield(IntType),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Enum {
+    pub name: String,
+    pub doc: String,
+    pub values: Vec<EnumValue>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumValue {
+    pub name: String,
+    pub doc: String,
+    pub value: u32,
+}
+
+impl FromStr for IntType {
+    type Err = String;
+
+    fn from_str(ty: &str) -> Result<Self, Self::Err> {
+        let int_type = match ty {
+            "u8" => Self::U8,
+            "i8" => Self::I8,
+            "u16" => Self::U16,
+            "i16" => Self::I16,
+            "u32" => Self::U32,
+            "i32" => Self::I32,
+            "u64" => Self::U64,
+            "i64" => Self::I64,
+            _ => return Err(format!("Unknown integer type `{}`", ty)),
+        };
+
+        Ok(int_type)
+    }
+}
+
+impl Feature {
+    /// Parses the `<feature>` (or the older `<project>`) definition
+    pub fn parse(source: &str) -> Result<Self, Error> {
+        let root = xml::parse(source)?;
+        if root.name != "feature" && root.name != "project" {
+            return Err(definition(format!(
+                "Expected a `<feature>`, got `<{}>`",
+                root.name
+            )));
+        }
+
+        let name = required(&root, "name")?.to_string();
+        let enums = root
+            .elements("enums")
+            .flat_map(|enums| enums.elements("enum"))
+            .map(|enum_element| Enum::parse(enum_element).map_err(|err| context(err, &name)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let classes = match root.elements("msgs").next() {
+            Some(msgs) => {
+                vec![Class::parse_msgs(msgs, &enums).map_err(|err| context(err, &name))?]
+            }
+            None => root
+                .elements("class")
+                .map(|class| Class::parse(class).map_err(|err| context(err, &name)))
+                .collect::<Result<_, _>>()?,
+        };
+
+        Ok(Self {
+            id: parse_attribute(&root, "id")?,
+            doc: root.text(),
+            name,
+            classes,
+            enums,
+        })
+    }
+}
+
+impl Class {
+    fn parse(class: &Element) -> Result<Self, Error> {
+        let name = required(class, "name")?.to_string();
+        let commands = class
+            .elements("cmd")
+            .map(|command| Command::parse(command).map_err(|err| context(err, &name)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            id: parse_attribute(class, "id")?,
+            doc: class.text(),
+            name,
+            commands,
+        })
+    }
+
+    /// The `<cmd>`s and `<evt>`s of the `<msgs>`, which may use the `enums` of the feature
+    fn parse_msgs(msgs: &Element, enums: &[Enum]) -> Result<Self, Error> {
+        let commands = msgs
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                xml::Node::Element(element) if element.name == "cmd" || element.name == "evt" => {
+                    Some(element)
+                }
+                _ => None,
+            })
+            .map(|command| Command::parse(command).map_err(|err| context(err, "Msgs")))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for command in &commands {
+            for arg in &command.args {
+                if let ArgType::NamedEnum(enum_name) = &arg.ty {
+                    if !enums.iter().any(|enum_type| &enum_type.name == enum_name) {
+                        return Err(definition(format!(
+                            "Msgs: {}: Unknown enum `{}`",
+                            command.name, enum_name
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            id: 0,
+            name: "Msgs".to_string(),
+            doc: String::new(),
+            commands,
+        })
+    }
+}
+
+impl Enum {
+    fn parse(enum_element: &Element) -> Result<Self, Error> {
+        let name = required(enum_element, "name")?.to_string();
+        let values = parse_enum_values(enum_element, "value").map_err(|err| context(err, &name))?;
+
+        Ok(Self {
+            doc: enum_element.text(),
+            name,
+            values,
+        })
+    }
+}
+
+impl Command {
+    fn parse(command: &Element) -> Result<Self, Error> {
+        let name = required(command, "name")?.to_string();
+        let args = command
+            .elements("arg")
+            .map(|arg| Arg::parse(arg).map_err(|err| context(err, &name)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            id: parse_attribute(command, "id")?,
+            doc: doc(command),
+            deprecated: command.attribute("deprecated") == Some("true"),
+            name,
+            args,
+        })
+    }
+}
+
+impl Arg {
+    fn parse(arg: &Element) -> Result<Self, Error> {
+        let name = required(arg, "name")?.to_string();
+        let ty = match required(arg, "type")? {
+            "float" => ArgType::Float,
+            "double" => ArgType::Double,
+            "string" => ArgType::String,
+            "enum" => ArgType::Enum(parse_enum_values(arg, "enum")?),
+            // e.g. `enum:control_mode`
+            ty if ty.starts_with("enum:") => ArgType::NamedEnum(ty["enum:".len()..].to_string()),
+            // e.g. `bitfield:u8:home_type`
+            ty if ty.starts_with("bitfield:") => ty
+                .split(':')
+                .nth(1)
+                .unwrap_or_default()
+                .parse()
+                .map(ArgType::Bitfield)
+                .map_err(definition)?,
+            ty => ty.parse().map(ArgType::Int).map_err(definition)?,
+        };
+
+        Ok(Self {
+            doc: arg.text(),
+            name,
+            ty,
+        })
+    }
+}
+
+/// The values are numbered from `0` unless they have a `value`
+fn parse_enum_values(element: &Element, value_element: &str) -> Result<Vec<EnumValue>, Error> {
+    let mut next_value = 0;
+    let values = element
+        .elements(value_element)
+        .map(|value| {
+            let enum_value = EnumValue {
+                name: required(value, "name")?.to_string(),
+                doc: value.text(),
+                value: match value.attribute("value") {
+                    Some(_) => parse_attribute(value, "value")?,
+                    None => next_value,
+                },
+            };
+            next_value = enum_value.value + 1;
+
+            Ok(enum_value)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    if values.is_empty() {
+        return Err(definition(format!(
+            "Enum `{}` has no values",
+            element.attribute("name").unwrap_or_default()
+        )));
+    }
+
+    Ok(values)
+}
+
+/// The `title` of the `<comment>` or the text of the element
+fn doc(element: &Element) -> String {
+    element
+        .elements("comment")
+        .next()
+        .and_then(|comment| comment.attribute("title"))
+        .map(str::to_string)
+        .unwrap_or_else(|| element.text())
+}
+
+fn required<'a>(element: &'a Element, name: &str) -> Result<&'a str, Error> {
+    element.attribute(name).ok_or_else(|| {
+        definition(format!(
+            "Missing the `{}` attribute of `<{}>`",
+            name, element.name
+        ))
+    })
+}
+
+fn parse_attribute<T: FromStr>(element: &Element, name: &str) -> Result<T, Error> {
+    let value = required(element, name)?;
+
+    value.parse().map_err(|_| {
+        definition(format!(
+            "Invalid `{}` attribute `{}` of `<{}>`",
+            name, value, element.name
+        ))
+    })
+}
+
+fn definition(message: impl Into<String>) -> Error {
+    Error::Definition(message.into())
+}
+
+/// Prefixes the error with where it happened, e.g. `ardrone3: Piloting: PCMD: ...`
+fn context(err: Error, name: &str) -> Error {
+    match err {
+        Error::Definition(message) => Error::Definition(format!("{}: {}", name, message)),
+        err => err,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_feature() {
+        let feature = Feature::parse(
+            r#"<feature id="3" name="jpsumo">
+                <class name="Piloting" id="0">
+                    <cmd name="Posture" id="1">
+                        <comment title="Change the posture"/>
+                        <arg name="type" type="enum">
+                            <enum name="standard">Standard posture</enum>
+                            <enum name="jumper"/>
+                            <enum name="kicker" value="5"/>
+                        </arg>
+                        <arg name="flags" type="bitfield:u8:type"/>
+                    </cmd>
+                </class>
+            </feature>"#,
+        )
+        .expect("Should parse");
+
+        assert_eq!(3, feature.id);
+        let command = &feature.classes[0].commands[0];
+        assert_eq!("Change the posture", command.doc);
+
+        match &command.args[0].ty {
+            ArgType::Enum(values) => {
+                assert_eq!("Standard posture", values[0].doc);
+                assert_eq!(
+                    vec![0, 1, 5],
+                    values.iter().map(|value| value.value).collect::<Vec<_>>()
+                );
+            }
+            ty => panic!("Unexpected type: {:?}", ty),
+        }
+        assert_eq!(ArgType::Bitfield(IntType::U8), command.args[1].ty);
+    }
+
+    #[test]
+    fn test_parse_msgs() {
+        let feature = Feature::parse(
+            r#"<feature id="148" name="gimbal">
+                <enums>
+                    <enum name="control_mode">
+                        Control mode
+                        <value name="position"/>
+                        <value name="velocity"/>
+                    </enum>
+                </enums>
+                <msgs>
+                    <cmd name="set_target" id="7">
+                        <arg name="gimbal_id" type="u8"/>
+                        <arg name="control_mode" type="enum:control_mode"/>
+                    </cmd>
+                    <evt name="attitude" id="6"/>
+                </msgs>
+            </feature>"#,
+        )
+        .expect("Should parse");
+
+        let class = &feature.classes[0];
+        assert_eq!((0, "Msgs"), (class.id, class.name.as_str()));
+        assert_eq!(
+            vec!["set_target", "attitude"],
+            class
+                .commands
+                .iter()
+                .map(|command| command.name.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            ArgType::NamedEnum("control_mode".to_string()),
+            class.commands[0].args[1].ty
+        );
+        assert_eq!("Control mode", feature.enums[0].doc);
+        assert_eq!(1, feature.enums[0].values[1].value);
+
+        let err = Feature::parse(
+            r#"<feature id="148" name="gimbal">
+                <msgs>
+                    <cmd name="set_target" id="7"><arg name="mode" type="enum:mode"/></cmd>
+                </msgs>
+            </feature>"#,
+        )
+        .expect_err("Should fail for an unknown enum");
+
+        assert_eq!(
+            "Invalid definition: gimbal: Msgs: set_target: Unknown enum `mode`",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_invalid_definition() {
+        let err = Feature::parse(
+            r#"<feature id="3" name="jpsumo">
+                <class name="Piloting" id="0">
+                    <cmd name="PCMD" id="0"><arg name="flag" type="bool"/></cmd>
+                </class>
+            </feature>"#,
+        )
+        .expect_err("Should fail for an unknown type");
+
+        assert_eq!(
+            "Invalid definition: jpsumo: Piloting: PCMD: Unknown integer type `bool`",
+            err.to_string()
+        );
+    }
+}
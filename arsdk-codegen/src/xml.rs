@@ -0,0 +1,289 @@
+//! A minimal XML parser, enough for the arsdk-xml definitions.
+//!
+//! Supports elements, attributes, text, comments, CDATA and the predefined entities.
+//! Namespaces and DTDs are not supported.
+use crate::Error;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Element(Element),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+impl Element {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The child elements with the given name
+    pub fn elements<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter_map(move |child| match child {
+            Node::Element(element) if element.name == name => Some(element),
+            _ => None,
+        })
+    }
+
+    /// The direct text of the element with the whitespace collapsed
+    pub fn text(&self) -> String {
+        self.children
+            .iter()
+            .filter_map(|child| match child {
+                Node::Text(text) => Some(text.as_str()),
+                Node::Element(_) => None,
+            })
+            .flat_map(str::split_whitespace)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Parses the document and returns its root element
+pub fn parse(source: &str) -> Result<Element, Error> {
+    let mut parser = Parser {
+        source,
+        position: 0,
+    };
+
+    parser.skip_misc()?;
+    let root = parser.element()?;
+    parser.skip_misc()?;
+
+    if parser.position != source.len() {
+        return Err(parser.error("Content after the root element"));
+    }
+
+    Ok(root)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    fn error(&self, message: &str) -> Error {
+        Error::Xml {
+            line: self.source[..self.position].matches('\n').count() + 1,
+            message: message.to_string(),
+        }
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), Error> {
+        if self.rest().starts_with(expected) {
+            self.position += expected.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("Expected `{}`", expected)))
+        }
+    }
+
+    /// Advances after the `end`, returning the skipped content
+    fn until(&mut self, end: &str) -> Result<&'a str, Error> {
+        match self.rest().find(end) {
+            Some(index) => {
+                let content = &self.rest()[..index];
+                self.position += index + end.len();
+                Ok(content)
+            }
+            None => Err(self.error(&format!("Missing `{}`", end))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.position = self.source.len() - trimmed.len();
+    }
+
+    /// Skips the whitespace, comments, processing instructions and the doctype
+    fn skip_misc(&mut self) -> Result<(), Error> {
+        loop {
+            self.skip_whitespace();
+
+            if self.rest().starts_with("<?") {
+                self.until("?>")?;
+            } else if self.rest().starts_with("<!--") {
+                self.until("-->")?;
+            } else if self.rest().starts_with("<!DOCTYPE") {
+                self.until(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<String, Error> {
+        let len = self
+            .rest()
+            .find(|c: char| !(c.is_alphanumeric() || "_-.:".contains(c)))
+            .unwrap_or_else(|| self.rest().len());
+
+        if len == 0 {
+            return Err(self.error("Expected a name"));
+        }
+
+        let name = self.rest()[..len].to_string();
+        self.position += len;
+
+        Ok(name)
+    }
+
+    fn element(&mut self) -> Result<Element, Error> {
+        self.expect("<")?;
+        let mut element = Element {
+            name: self.name()?,
+            ..Element::default()
+        };
+
+        loop {
+            self.skip_whitespace();
+
+            if self.rest().starts_with("/>") {
+                self.position += 2;
+                return Ok(element);
+            } else if self.rest().starts_with('>') {
+                self.position += 1;
+                break;
+            }
+
+            let name = self.name()?;
+            self.skip_whitespace();
+            self.expect("=")?;
+            self.skip_whitespace();
+
+            let quote = match self.rest().chars().next() {
+                Some(quote) if quote == '"' || quote == '\'' => quote,
+                _ => return Err(self.error("Expected a quoted attribute value")),
+            };
+            self.position += 1;
+            let value = self.until(&quote.to_string())?;
+
+            element.attributes.push((name, self.unescape(value)?));
+        }
+
+        loop {
+            if self.rest().starts_with("</") {
+                self.position += 2;
+                let name = self.name()?;
+                if name != element.name {
+                    return Err(self.error(&format!("Expected `</{}>`", element.name)));
+                }
+                self.skip_whitespace();
+                self.expect(">")?;
+
+                return Ok(element);
+            } else if self.rest().starts_with("<!--") {
+                self.until("-->")?;
+            } else if self.rest().starts_with("<![CDATA[") {
+                self.position += "<![CDATA[".len();
+                let text = self.until("]]>")?;
+                element.children.push(Node::Text(text.to_string()));
+            } else if self.rest().starts_with('<') {
+                element.children.push(Node::Element(self.element()?));
+            } else if self.rest().is_empty() {
+                return Err(self.error(&format!("Missing `</{}>`", element.name)));
+            } else {
+                let len = self.rest().find('<').unwrap_or_else(|| self.rest().len());
+                let text = &self.rest()[..len];
+                let text = self.unescape(text)?;
+                self.position += len;
+
+                element.children.push(Node::Text(text));
+            }
+        }
+    }
+
+    fn unescape(&self, text: &str) -> Result<String, Error> {
+        let mut unescaped = String::with_capacity(text.len());
+        let mut rest = text;
+
+        while let Some(start) = rest.find('&') {
+            unescaped.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let end = rest
+                .find(';')
+                .ok_or_else(|| self.error("Unterminated entity"))?;
+            let character = match &rest[1..end] {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                entity => entity
+                    .strip_prefix("#x")
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse))
+                    .and_then(Result::ok)
+                    .and_then(std::char::from_u32)
+                    .ok_or_else(|| self.error(&format!("Unknown entity `&{};`", entity)))?,
+            };
+
+            unescaped.push(character);
+            rest = &rest[end + 1..];
+        }
+
+        unescaped.push_str(rest);
+        Ok(unescaped)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let root = parse(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <!-- the feature -->
+            <feature id="1" name='ardrone3'>
+                All ARDrone3-only commands &amp; events
+                <class name="Piloting" id="0">
+                    <cmd name="TakeOff" id="1"><comment title="Take off"/></cmd>
+                    <![CDATA[<raw>]]>
+                </class>
+            </feature>"#,
+        )
+        .expect("Should parse");
+
+        assert_eq!("feature", root.name);
+        assert_eq!(Some("ardrone3"), root.attribute("name"));
+        assert_eq!("All ARDrone3-only commands & events", root.text());
+
+        let class = root.elements("class").next().expect("Should have a class");
+        assert_eq!("<raw>", class.text());
+
+        let cmd = class.elements("cmd").next().expect("Should have a cmd");
+        let comment = cmd
+            .elements("comment")
+            .next()
+            .expect("Should have a comment");
+        assert_eq!(Some("Take off"), comment.attribute("title"));
+    }
+
+    #[test]
+    fn test_errors() {
+        match parse("<feature>\n<class></feature>") {
+            Err(Error::Xml { line: 2, .. }) => {}
+            result => panic!("Unexpected result: {:?}", result),
+        }
+
+        assert!(parse("<feature id=1/>").is_err());
+        assert!(parse("<feature/><feature/>").is_err());
+        assert!(parse("<feature>&unknown;</feature>").is_err());
+    }
+}
@@ -0,0 +1,12 @@
+use std::{env, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=xml");
+
+    let out =
+        Path::new(&env::var("OUT_DIR").expect("OUT_DIR is set by cargo")).join("generated.rs");
+
+    if let Err(err) = arsdk_codegen::generate_dir("xml", out) {
+        panic!("Generating the commands: {}", err);
+    }
+}
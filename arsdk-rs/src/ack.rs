@@ -0,0 +1,360 @@
+use crate::{frame::BufferID, print_buf, Error};
+use dashmap::DashMap;
+use log::{error, warn};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll, Waker},
+    time::{Duration, Instant},
+};
+
+/// Returned for every sent Frame, it resolves once the Drone acknowledges the Frame.
+///
+/// Only `Type::DataWithAck` frames are acknowledged by the Drone,
+/// the handle of any other frame `Type` is resolved as soon as it's sent.
+///
+/// The handle can be either awaited or `wait()`-ed on:
+///
+/// ```ignore
+/// let ack = drone.send_frame(take_off_frame)?;
+/// // blocks until the Drone acknowledges the Frame or we give up re-sending it
+/// ack.wait()?;
+/// ```
+#[derive(Debug)]
+pub struct AckHandle {
+    buffer_id: BufferID,
+    sequence_id: u8,
+    waiter: Arc<AckWaiter>,
+}
+
+impl AckHandle {
+    /// A handle that will be resolved by the `PendingAcks`
+    pub(crate) fn pending(buffer_id: BufferID, sequence_id: u8) -> (Self, Arc<AckWaiter>) {
+        let waiter = Arc::new(AckWaiter::default());
+        let handle = Self {
+            buffer_id,
+            sequence_id,
+            waiter: waiter.clone(),
+        };
+
+        (handle, waiter)
+    }
+
+    /// A handle for frames which are not acknowledged by the Drone
+    pub(crate) fn resolved(buffer_id: BufferID, sequence_id: u8) -> Self {
+        let (handle, waiter) = Self::pending(buffer_id, sequence_id);
+        waiter.resolve(Ok(()));
+
+        handle
+    }
+
+    pub fn buffer_id(&self) -> BufferID {
+        self.buffer_id
+    }
+
+    pub fn sequence_id(&self) -> u8 {
+        self.sequence_id
+    }
+
+    /// Blocks until the Drone acknowledges the Frame.
+    ///
+    /// Returns `Error::AckTimeout` if the frame was re-sent `Config::ack_retries` times
+    /// without an Ack from the Drone.
+    pub fn wait(self) -> Result<(), Error> {
+        let mut state = self.waiter.lock();
+
+        loop {
+            if let Some(result) = state.result.take() {
+                return result;
+            }
+
+            state = self
+                .waiter
+                .resolved
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+impl Future for AckHandle {
+    type Output = Result<(), Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.waiter.lock();
+
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct AckState {
+    result: Option<Result<(), Error>>,
+    waker: Option<Waker>,
+}
+
+/// The resolving side of an `AckHandle`
+#[derive(Debug, Default)]
+pub(crate) struct AckWaiter {
+    state: Mutex<AckState>,
+    resolved: Condvar,
+}
+
+impl AckWaiter {
+    fn lock(&self) -> MutexGuard<'_, AckState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn resolve(&self, result: Result<(), Error>) {
+        let mut state = self.lock();
+        state.result = Some(result);
+
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+
+        self.resolved.notify_all();
+    }
+}
+
+/// A sent `Type::DataWithAck` frame waiting for the Drone's Ack
+#[derive(Debug)]
+struct Pending {
+    raw_message: Vec<u8>,
+    sent_at: Instant,
+    retries_left: u8,
+    /// Resolved once the frame is acknowledged or all the retries have been used
+    waiter: Arc<AckWaiter>,
+}
+
+/// The shortest period in which the frames to re-send are checked, even for a `0` timeout
+const MIN_CHECK_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Keeps track of the outstanding `Type::DataWithAck` frames.
+///
+/// Like ARNetwork, each frame is identified by it's `BufferID` and sequence id
+/// and it's re-sent (with the same sequence id) until the Drone acknowledges it.
+#[derive(Debug)]
+pub(crate) struct PendingAcks {
+    timeout: Duration,
+    retries: u8,
+    pending: DashMap<(BufferID, u8), Pending>,
+}
+
+impl PendingAcks {
+    pub(crate) fn new(timeout: Duration, retries: u8) -> Self {
+        Self {
+            timeout,
+            retries,
+            pending: DashMap::new(),
+        }
+    }
+
+    /// How often the frames to re-send should be checked,
+    /// a few times in the timeout period to not re-send the frames too late
+    pub(crate) fn check_interval(&self) -> Duration {
+        (self.timeout / 4).max(MIN_CHECK_INTERVAL)
+    }
+
+    pub(crate) fn insert(
+        &self,
+        buffer_id: BufferID,
+        sequence_id: u8,
+        raw_message: Vec<u8>,
+        waiter: Arc<AckWaiter>,
+    ) {
+        let pending = Pending {
+            raw_message,
+            sent_at: Instant::now(),
+            retries_left: self.retries,
+            waiter,
+        };
+
+        if let Some(overwritten) = self.pending.insert((buffer_id, sequence_id), pending) {
+            // the sequence id wrapped around before the Drone acknowledged the old frame
+            Self::resolve(
+                overwritten,
+                Err(Error::AckTimeout {
+                    buffer_id,
+                    sequence_id,
+                }),
+            );
+        }
+    }
+
+    /// Returns `true` if the Ack matched a frame that we've sent
+    pub(crate) fn acknowledge(&self, buffer_id: BufferID, sequence_id: u8) -> bool {
+        match self.pending.remove(&(buffer_id, sequence_id)) {
+            Some((_, pending)) => {
+                Self::resolve(pending, Ok(()));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stops re-sending a frame, e.g. one that couldn't be sent in the first place
+    pub(crate) fn remove(&self, buffer_id: BufferID, sequence_id: u8) {
+        self.pending.remove(&(buffer_id, sequence_id));
+    }
+
+    /// Returns the raw messages which haven't been acknowledged in time and should be re-sent.
+    ///
+    /// Frames that have no retries left are dropped and resolved with `Error::AckTimeout`.
+    pub(crate) fn retransmissions(&self, now: Instant) -> Vec<Vec<u8>> {
+        let mut resend = Vec::new();
+        let mut timed_out = Vec::new();
+
+        self.pending.retain(|&key, pending| {
+            if now.saturating_duration_since(pending.sent_at) < self.timeout {
+                return true;
+            }
+
+            if pending.retries_left > 0 {
+                pending.retries_left -= 1;
+                pending.sent_at = now;
+                resend.push(pending.raw_message.clone());
+
+                true
+            } else {
+                timed_out.push((key, pending.waiter.clone()));
+
+                false
+            }
+        });
+
+        for ((buffer_id, sequence_id), waiter) in timed_out {
+            error!(
+                "No Ack received for {} with sequence id {}",
+                buffer_id, sequence_id
+            );
+
+            waiter.resolve(Err(Error::AckTimeout {
+                buffer_id,
+                sequence_id,
+            }));
+        }
+
+        resend
+    }
+
+    /// Resolves all the frames with `Error::Disconnected`, they won't be re-sent anymore
+    pub(crate) fn cancel(&self) {
+        let pending = self
+            .pending
+            .iter()
+            .map(|entry| *entry.key())
+            .collect::<Vec<_>>();
+
+        for key in pending {
+            if let Some((_, pending)) = self.pending.remove(&key) {
+                Self::resolve(pending, Err(Error::Disconnected));
+            }
+        }
+    }
+
+    fn resolve(pending: Pending, result: Result<(), Error>) {
+        if let Err(err) = &result {
+            warn!("{} - Bytes: {}", err, print_buf(&pending.raw_message));
+        }
+
+        pending.waiter.resolve(result);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{
+        sync::atomic::{AtomicBool, Ordering},
+        task::Wake,
+    };
+
+    #[test]
+    fn acknowledged_frame_is_not_resent() {
+        let acks = PendingAcks::new(Duration::from_millis(10), 2);
+        let (handle, waiter) = AckHandle::pending(BufferID::CDAck, 1);
+
+        acks.insert(BufferID::CDAck, 1, vec![4, 11, 1], waiter);
+
+        assert!(!acks.acknowledge(BufferID::CDAck, 2));
+        assert!(acks.acknowledge(BufferID::CDAck, 1));
+        assert!(handle.wait().is_ok());
+
+        let later = Instant::now() + Duration::from_millis(20);
+        assert!(acks.retransmissions(later).is_empty());
+    }
+
+    #[test]
+    fn check_interval_is_never_zero() {
+        let acks = PendingAcks::new(Duration::from_millis(100), 2);
+        assert_eq!(Duration::from_millis(25), acks.check_interval());
+
+        let acks = PendingAcks::new(Duration::from_nanos(3), 2);
+        assert_eq!(MIN_CHECK_INTERVAL, acks.check_interval());
+    }
+
+    #[test]
+    fn frame_is_resent_until_retries_are_exhausted() {
+        let acks = PendingAcks::new(Duration::from_millis(10), 2);
+        let (handle, waiter) = AckHandle::pending(BufferID::CDAck, 7);
+
+        acks.insert(BufferID::CDAck, 7, vec![4, 11, 7], waiter);
+
+        let mut now = Instant::now();
+        assert!(acks.retransmissions(now).is_empty());
+
+        for _ in 0..2 {
+            now += Duration::from_millis(10);
+            assert_eq!(vec![vec![4, 11, 7]], acks.retransmissions(now));
+        }
+
+        now += Duration::from_millis(10);
+        assert!(acks.retransmissions(now).is_empty());
+
+        match handle.wait() {
+            Err(Error::AckTimeout {
+                buffer_id: BufferID::CDAck,
+                sequence_id: 7,
+            }) => {}
+            result => panic!("Expected AckTimeout, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn ack_handle_future_is_woken_on_ack() {
+        #[derive(Default)]
+        struct Flag(AtomicBool);
+
+        impl Wake for Flag {
+            fn wake(self: Arc<Self>) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let acks = PendingAcks::new(Duration::from_millis(10), 2);
+        let (mut handle, waiter) = AckHandle::pending(BufferID::CDAck, 3);
+        acks.insert(BufferID::CDAck, 3, vec![4, 11, 3], waiter);
+
+        let flag = Arc::new(Flag::default());
+        let waker = Waker::from(flag.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        assert!(Pin::new(&mut handle).poll(&mut cx).is_pending());
+        assert!(acks.acknowledge(BufferID::CDAck, 3));
+        assert!(flag.0.load(Ordering::SeqCst));
+
+        match Pin::new(&mut handle).poll(&mut cx) {
+            Poll::Ready(Ok(())) => {}
+            poll => panic!("Expected a resolved Ack, got {:?}", poll),
+        }
+    }
+}
@@ -2,11 +2,15 @@ mod gps_state;
 mod piloting;
 mod piloting_state;
 
-pub use gps_state::GPSState;
+pub use gps_state::{GPSState, HomeType};
 pub use piloting::{pcmd::PCMD, Piloting};
-pub use piloting_state::PilotingState;
+pub use piloting_state::{
+    AlertState, FlyingState, LandingState, Motion, MoveToStatus, NavigateHomeReason,
+    NavigateHomeState, OrientationMode, PilotedPOIStatus, PilotingState,
+    ReturnHomeBatteryCapacityStatus,
+};
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 /// u8
 pub enum ArDrone3 {
     /// ARCOMMANDS_ID_ARDRONE3_CLASS_PILOTING = 0
@@ -109,8 +113,7 @@ pub enum ArDrone3 {
     /// ARCOMMANDS_ID_ARDRONE3_CLASS_ANTIFLICKERINGSTATE = 30
     AntiFlickeringState,
     /// ARCOMMANDS_ID_ARDRONE3_CLASS_GPSSTATE = 31
-    /// TODO: use the GPSState struct
-    GPSState,
+    GPSState(GPSState),
     /// ARCOMMANDS_ID_ARDRONE3_CLASS_PROSTATE = 32
     ProState,
     /// ARCOMMANDS_ID_ARDRONE3_CLASS_ACCESSORYSTATE = 33
@@ -155,7 +158,7 @@ impl Into<u8> for &ArDrone3 {
             ArDrone3::CameraState => 28,
             ArDrone3::AntiFlickering => 29,
             ArDrone3::AntiFlickeringState => 30,
-            ArDrone3::GPSState => 31,
+            ArDrone3::GPSState(_) => 31,
             ArDrone3::ProState => 32,
             ArDrone3::AccessoryState => 33,
             ArDrone3::PilotingEvent => 34,
@@ -222,7 +225,7 @@ pub mod scroll_impl {
                 // 25 => Self::CameraState,
                 // 29 => Self::AntiFlickering,
                 // 30 => Self::AntiFlickeringState,
-                // 31 => Self::GPSState,
+                31 => Self::GPSState(src.gread_with(&mut offset, ctx)?),
                 // 32 => Self::ProState,
                 // 33 => Self::AccessoryState,
                 // 34 => Self::PilotingEvent,
@@ -264,7 +267,15 @@ pub mod scroll_impl {
                 Self::PilotingState(piloting_state) => {
                     this.gwrite_with(piloting_state, &mut offset, ctx)?;
                 }
-                _ => unimplemented!("Not all ArDrone3 Classes are impled!"),
+                Self::GPSState(gps_state) => {
+                    this.gwrite_with(gps_state, &mut offset, ctx)?;
+                }
+                not_impled => {
+                    return Err(Self::Error::NotImplemented(format!(
+                        "ArDrone3 Class {:?}",
+                        not_impled
+                    )))
+                }
             }
 
             Ok(offset)
@@ -10,28 +10,192 @@ pub enum GPSState {
     NumberOfSatelliteChanged(u8),
     /// ARCOMMANDS_ID_ARDRONE3_GPSSTATE_CMD_HOMETYPEAVAILABILITYCHANGED = 1
     ///
-    /// 1. Type (u32):
-    /// - ARCOMMANDS_ARDRONE3_GPSSTATE_HOMETYPEAVAILABILITYCHANGED_TYPE_TAKEOFF = 0
-    ///     > The drone has enough information to return to the take off position
-    /// - ARCOMMANDS_ARDRONE3_GPSSTATE_HOMETYPEAVAILABILITYCHANGED_TYPE_PILOT = 1
-    ///     > The drone has enough information to return to the pilot position
-    /// - ARCOMMANDS_ARDRONE3_GPSSTATE_HOMETYPEAVAILABILITYCHANGED_TYPE_FIRST_FIX = 2
-    ///     > The drone has not enough information, it will return to the first GPS fix
-    /// - ARCOMMANDS_ARDRONE3_GPSSTATE_HOMETYPEAVAILABILITYCHANGED_TYPE_FOLLOWEE = 3
-    ///     > The drone has enough information to return to the target of the current (or last) follow me
-    /// - ARCOMMANDS_ARDRONE3_GPSSTATE_HOMETYPEAVAILABILITYCHANGED_TYPE_MAX
-    ///  TODO: Check what the `MAX` does!
+    /// 1. Type (u32): `HomeType`
     ///
     /// Last argument is:
     /// - uint8_t *_available
     ///     > 1 if this type is available, 0 otherwise
-    HomeTypeAvailabilityChanged,
+    HomeTypeAvailabilityChanged {
+        home_type: HomeType,
+        available: bool,
+    },
     /// ARCOMMANDS_ID_ARDRONE3_GPSSTATE_CMD_HOMETYPECHOSENCHANGED = 2,
-    HomeTypeChosenChanged,
+    ///
+    /// The home type that the Drone will use for returning home
+    HomeTypeChosenChanged(HomeType),
+    Unknown {
+        gps_state: u16,
+        data: Vec<u8>,
+    },
+}
+
+/// eARCOMMANDS_ARDRONE3_GPSSTATE_HOMETYPEAVAILABILITYCHANGED_TYPE
+/// and eARCOMMANDS_ARDRONE3_GPSSTATE_HOMETYPECHOSENCHANGED_TYPE
+/// u32
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum HomeType {
+    /// ARCOMMANDS_ARDRONE3_GPSSTATE_HOMETYPEAVAILABILITYCHANGED_TYPE_TAKEOFF = 0
+    ///
+    /// The drone has enough information to return to the take off position
+    TakeOff = 0,
+    /// ARCOMMANDS_ARDRONE3_GPSSTATE_HOMETYPEAVAILABILITYCHANGED_TYPE_PILOT = 1
+    ///
+    /// The drone has enough information to return to the pilot position
+    Pilot = 1,
+    /// ARCOMMANDS_ARDRONE3_GPSSTATE_HOMETYPEAVAILABILITYCHANGED_TYPE_FIRST_FIX = 2
+    ///
+    /// The drone has not enough information, it will return to the first GPS fix
+    FirstFix = 2,
+    /// ARCOMMANDS_ARDRONE3_GPSSTATE_HOMETYPEAVAILABILITYCHANGED_TYPE_FOLLOWEE = 3
+    ///
+    /// The drone has enough information to return to the target of the current (or last) follow me
+    Followee = 3,
+}
+
+impl From<&GPSState> for u16 {
+    fn from(gps_state: &GPSState) -> Self {
+        use GPSState::*;
+
+        match gps_state {
+            NumberOfSatelliteChanged(_) => 0,
+            HomeTypeAvailabilityChanged { .. } => 1,
+            HomeTypeChosenChanged(_) => 2,
+            Unknown { gps_state, .. } => *gps_state,
+        }
+    }
 }
 
 mod scroll_impl {
-    // TODO: impl TryFromCtx
+    use super::*;
+    use crate::{frame::Error, parse::read_unknown};
+    use scroll::{ctx, Endian, Pread, Pwrite};
+
+    impl<'a> ctx::TryFromCtx<'a, Endian> for GPSState {
+        type Error = Error;
+
+        fn try_from_ctx(src: &'a [u8], ctx: Endian) -> Result<(Self, usize), Self::Error> {
+            let mut offset = 0;
+
+            let gps_state = match src.gread_with::<u16>(&mut offset, ctx)? {
+                0 => Self::NumberOfSatelliteChanged(src.gread_with(&mut offset, ctx)?),
+                1 => Self::HomeTypeAvailabilityChanged {
+                    home_type: src.gread_with(&mut offset, ctx)?,
+                    available: src.gread_with::<u8>(&mut offset, ctx)? == 1,
+                },
+                2 => Self::HomeTypeChosenChanged(src.gread_with(&mut offset, ctx)?),
+                unknown => Self::Unknown {
+                    gps_state: unknown,
+                    data: read_unknown(src, &mut offset)?,
+                },
+            };
+
+            Ok((gps_state, offset))
+        }
+    }
+
+    impl ctx::TryIntoCtx<Endian> for GPSState {
+        type Error = Error;
+
+        fn try_into_ctx(self, this: &mut [u8], ctx: Endian) -> Result<usize, Self::Error> {
+            let mut offset = 0;
+
+            this.gwrite_with::<u16>((&self).into(), &mut offset, ctx)?;
+
+            match self {
+                Self::NumberOfSatelliteChanged(satellites) => {
+                    this.gwrite_with(satellites, &mut offset, ctx)?;
+                }
+                Self::HomeTypeAvailabilityChanged {
+                    home_type,
+                    available,
+                } => {
+                    this.gwrite_with(home_type, &mut offset, ctx)?;
+                    this.gwrite_with::<u8>(available.into(), &mut offset, ctx)?;
+                }
+                Self::HomeTypeChosenChanged(home_type) => {
+                    this.gwrite_with(home_type, &mut offset, ctx)?;
+                }
+                Self::Unknown { data, .. } => {
+                    this.gwrite_with(data.as_slice(), &mut offset, ())?;
+                }
+            }
+
+            Ok(offset)
+        }
+    }
+
+    impl<'a> ctx::TryFromCtx<'a, Endian> for HomeType {
+        type Error = Error;
+
+        fn try_from_ctx(src: &'a [u8], ctx: Endian) -> Result<(Self, usize), Self::Error> {
+            let mut offset = 0;
+
+            let home_type = match src.gread_with::<u32>(&mut offset, ctx)? {
+                0 => Self::TakeOff,
+                1 => Self::Pilot,
+                2 => Self::FirstFix,
+                3 => Self::Followee,
+                value => {
+                    return Err(Error::OutOfBound {
+                        value: value.into(),
+                        param: "HomeType".to_string(),
+                    })
+                }
+            };
+
+            Ok((home_type, offset))
+        }
+    }
+
+    impl ctx::TryIntoCtx<Endian> for HomeType {
+        type Error = Error;
+
+        fn try_into_ctx(self, this: &mut [u8], ctx: Endian) -> Result<usize, Self::Error> {
+            Ok(this.pwrite_with::<u32>(self as u32, 0, ctx)?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scroll::{Pread, Pwrite, LE};
+
+    fn assert_round_trip(gps_state: GPSState, expected_bytes: &[u8]) {
+        let mut buf = [0_u8; 16];
+        let written = buf
+            .pwrite_with(gps_state.clone(), 0, LE)
+            .expect("Should serialize GPSState");
+
+        assert_eq!(expected_bytes, &buf[..written]);
+        assert_eq!(
+            gps_state,
+            expected_bytes
+                .pread_with::<GPSState>(0, LE)
+                .expect("Should deserialize GPSState")
+        );
+    }
 
-    // TODO: impl TryIntoCtx
+    #[test]
+    fn test_gps_state() {
+        assert_round_trip(GPSState::NumberOfSatelliteChanged(12), &[0, 0, 12]);
+        assert_round_trip(
+            GPSState::HomeTypeAvailabilityChanged {
+                home_type: HomeType::FirstFix,
+                available: true,
+            },
+            &[1, 0, 2, 0, 0, 0, 1],
+        );
+        assert_round_trip(
+            GPSState::HomeTypeChosenChanged(HomeType::Pilot),
+            &[2, 0, 1, 0, 0, 0],
+        );
+        assert_round_trip(
+            GPSState::Unknown {
+                gps_state: 3,
+                data: vec![1, 2],
+            },
+            &[3, 0, 1, 2],
+        );
+    }
 }
@@ -1,5 +1,5 @@
 /// u16
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PilotingState {
     /// typedef enum {
     ///     ARCOMMANDS_ID_ARDRONE3_PILOTINGSTATE_CMD_FLATTRIMCHANGED = 0,
@@ -20,13 +20,24 @@ pub enum PilotingState {
     /// Frame { frame_type: DataWithAck, buffer_id: DCEvent, sequence_id: 0, feature: Some(ArDrone3(Some(Unknown { ardrone3: 4, data: [1, 0, 0, 0, 0, 0] }))) }
     /// ```
     /// u16 [1, 0] - FLYINGSTATECHANGED
-    FlyingStateChanged,
+    /// u32 [7, 0, 0, 0] - FlyingState::MotorRamping
+    FlyingStateChanged(FlyingState),
     /// ARCOMMANDS_ID_ARDRONE3_PILOTINGSTATE_CMD_ALERTSTATECHANGED = 2,
-    AlterStateChanged,
+    AlertStateChanged(AlertState),
     /// ARCOMMANDS_ID_ARDRONE3_PILOTINGSTATE_CMD_NAVIGATEHOMESTATECHANGED = 3,
-    NavigateHomeStateChanged,
+    NavigateHomeStateChanged {
+        state: NavigateHomeState,
+        reason: NavigateHomeReason,
+    },
     /// ARCOMMANDS_ID_ARDRONE3_PILOTINGSTATE_CMD_POSITIONCHANGED = 4,
-    PositionChanged,
+    ///
+    /// Latitude and Longitude in decimal degrees, Altitude in meters.
+    /// Each of them is `500.0` if not available.
+    PositionChanged {
+        latitude: f64,
+        longitude: f64,
+        altitude: f64,
+    },
     /// ARCOMMANDS_ID_ARDRONE3_PILOTINGSTATE_CMD_SPEEDCHANGED = 5,
     ///
     /// Example:
@@ -40,16 +51,43 @@ pub enum PilotingState {
     /// currIndexInBuffer = ARCOMMANDS_ReadWrite_AddFloatToBuffer (buffer, _speedY, currIndexInBuffer, buffLen);
     /// currIndexInBuffer = ARCOMMANDS_ReadWrite_AddFloatToBuffer (buffer, _speedZ, currIndexInBuffer, buffLen);
     /// ```
-    SpeedChanged,
+    ///
+    /// Speed in m/s relative to the north (`speed_x`), east (`speed_y`) and ground (`speed_z`)
+    SpeedChanged {
+        speed_x: f32,
+        speed_y: f32,
+        speed_z: f32,
+    },
     /// ARCOMMANDS_ID_ARDRONE3_PILOTINGSTATE_CMD_ATTITUDECHANGED = 6,
     /// Frame { frame_type: Data, buffer_id: DCNavdata, sequence_id: 40, feature: Some(ArDrone3(Some(PilotingState { data: [6, 0, 44, 49, 49, 55, 153, 38, 7, 185, 107, 25, 201, 63] }))) }
-    AttitudeChanged,
+    ///
+    /// Angles in radians
+    AttitudeChanged {
+        roll: f32,
+        pitch: f32,
+        yaw: f32,
+    },
     /// ARCOMMANDS_ID_ARDRONE3_PILOTINGSTATE_CMD_AUTOTAKEOFFMODECHANGED = 7,
-    AutoTakeOffModeChanged,
+    ///
+    /// bool: u8
+    AutoTakeOffModeChanged(bool),
     /// ARCOMMANDS_ID_ARDRONE3_PILOTINGSTATE_CMD_ALTITUDECHANGED = 8,
-    AltitudeChanged,
+    ///
+    /// Altitude in meters relative to the take off point
+    AltitudeChanged(f64),
     /// ARCOMMANDS_ID_ARDRONE3_PILOTINGSTATE_CMD_GPSLOCATIONCHANGED = 9,
-    GpsLocationChanged,
+    ///
+    /// Latitude and Longitude in decimal degrees, Altitude in meters.
+    /// Each of them is `500.0` if not available.
+    /// The accuracies are in meters.
+    GpsLocationChanged {
+        latitude: f64,
+        longitude: f64,
+        altitude: f64,
+        latitude_accuracy: i8,
+        longitude_accuracy: i8,
+        altitude_accuracy: i8,
+    },
     /// ARCOMMANDS_ID_ARDRONE3_PILOTINGSTATE_CMD_LANDINGSTATECHANGED = 10,
     ///
     /// ```c
@@ -63,44 +101,302 @@ pub enum PilotingState {
     ///     ARCOMMANDS_ARDRONE3_PILOTINGSTATE_LANDINGSTATECHANGED_STATE_MAX
     /// } eARCOMMANDS_ARDRONE3_PILOTINGSTATE_LANDINGSTATECHANGED_STATE;
     /// ```
-    LandingStateChanged,
+    LandingStateChanged(LandingState),
     /// ARCOMMANDS_ID_ARDRONE3_PILOTINGSTATE_CMD_AIRSPEEDCHANGED = 11,
-    AirspeedChanged,
+    ///
+    /// Speed relative to the air in m/s
+    AirspeedChanged(f32),
     ///  ARCOMMANDS_ID_ARDRONE3_PILOTINGSTATE_CMD_MOVETOCHANGED = 12,
-    MoveToChanged,
+    ///
+    /// Latitude and Longitude in decimal degrees, Altitude in meters and Heading in degrees
+    MoveToChanged {
+        latitude: f64,
+        longitude: f64,
+        altitude: f64,
+        orientation_mode: OrientationMode,
+        heading: f32,
+        status: MoveToStatus,
+    },
     ///  ARCOMMANDS_ID_ARDRONE3_PILOTINGSTATE_CMD_MOTIONSTATE = 13,
-    MotionState,
+    MotionState(Motion),
     /// ARCOMMANDS_ID_ARDRONE3_PILOTINGSTATE_CMD_PILOTEDPOI = 14,
-    PilotedPOI,
+    ///
+    /// Latitude and Longitude in decimal degrees, Altitude in meters
+    PilotedPOI {
+        latitude: f64,
+        longitude: f64,
+        altitude: f64,
+        status: PilotedPOIStatus,
+    },
     /// ARCOMMANDS_ID_ARDRONE3_PILOTINGSTATE_CMD_RETURNHOMEBATTERYCAPACITY = 15,
-    ReturnHomeBatteryCapacity,
+    ReturnHomeBatteryCapacity(ReturnHomeBatteryCapacityStatus),
     Unknown {
         piloting_state: u16,
         data: Vec<u8>,
     },
 }
 
-impl Into<u16> for &PilotingState {
-    fn into(self) -> u16 {
+/// eARCOMMANDS_ARDRONE3_PILOTINGSTATE_FLYINGSTATECHANGED_STATE
+/// u32
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum FlyingState {
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_FLYINGSTATECHANGED_STATE_LANDED = 0
+    Landed = 0,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_FLYINGSTATECHANGED_STATE_TAKINGOFF = 1
+    TakingOff = 1,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_FLYINGSTATECHANGED_STATE_HOVERING = 2
+    Hovering = 2,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_FLYINGSTATECHANGED_STATE_FLYING = 3
+    Flying = 3,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_FLYINGSTATECHANGED_STATE_LANDING = 4
+    Landing = 4,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_FLYINGSTATECHANGED_STATE_EMERGENCY = 5
+    Emergency = 5,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_FLYINGSTATECHANGED_STATE_USERTAKEOFF = 6
+    ///
+    /// Waiting for the user action to take off
+    UserTakeOff = 6,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_FLYINGSTATECHANGED_STATE_MOTOR_RAMPING = 7
+    MotorRamping = 7,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_FLYINGSTATECHANGED_STATE_EMERGENCY_LANDING = 8
+    EmergencyLanding = 8,
+}
+
+/// eARCOMMANDS_ARDRONE3_PILOTINGSTATE_ALERTSTATECHANGED_STATE
+/// u32
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AlertState {
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_ALERTSTATECHANGED_STATE_NONE = 0
+    ///
+    /// No alert
+    None = 0,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_ALERTSTATECHANGED_STATE_USER = 1
+    ///
+    /// User emergency alert
+    User = 1,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_ALERTSTATECHANGED_STATE_CUT_OUT = 2
+    ///
+    /// Cut out alert
+    CutOut = 2,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_ALERTSTATECHANGED_STATE_CRITICAL_BATTERY = 3
+    ///
+    /// Critical battery alert
+    CriticalBattery = 3,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_ALERTSTATECHANGED_STATE_LOW_BATTERY = 4
+    ///
+    /// Low battery alert
+    LowBattery = 4,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_ALERTSTATECHANGED_STATE_TOO_MUCH_ANGLE = 5
+    ///
+    /// The angle of the drone is too high
+    TooMuchAngle = 5,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_ALERTSTATECHANGED_STATE_ALMOST_EMPTY_BATTERY = 6
+    ///
+    /// Almost empty battery alert
+    AlmostEmptyBattery = 6,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_ALERTSTATECHANGED_STATE_MAGNETO_PERTUBATION = 7
+    ///
+    /// Magnetometer is disturbed by a magnetic element
+    MagnetoPertubation = 7,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_ALERTSTATECHANGED_STATE_MAGNETO_LOW_EARTH_FIELD = 8
+    ///
+    /// Local terrestrial magnetic field is too weak
+    MagnetoLowEarthField = 8,
+}
+
+/// eARCOMMANDS_ARDRONE3_PILOTINGSTATE_NAVIGATEHOMESTATECHANGED_STATE
+/// u32
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum NavigateHomeState {
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_NAVIGATEHOMESTATECHANGED_STATE_AVAILABLE = 0
+    ///
+    /// Navigate home is available
+    Available = 0,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_NAVIGATEHOMESTATECHANGED_STATE_INPROGRESS = 1
+    ///
+    /// Navigate home is in progress
+    InProgress = 1,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_NAVIGATEHOMESTATECHANGED_STATE_UNAVAILABLE = 2
+    ///
+    /// Navigate home is not available
+    Unavailable = 2,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_NAVIGATEHOMESTATECHANGED_STATE_PENDING = 3
+    ///
+    /// Navigate home has been received, but its process is pending
+    Pending = 3,
+}
+
+/// eARCOMMANDS_ARDRONE3_PILOTINGSTATE_NAVIGATEHOMESTATECHANGED_REASON
+/// u32
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum NavigateHomeReason {
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_NAVIGATEHOMESTATECHANGED_REASON_USERREQUEST = 0
+    ///
+    /// User requested a navigate home
+    UserRequest = 0,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_NAVIGATEHOMESTATECHANGED_REASON_CONNECTIONLOST = 1
+    ///
+    /// Connection between controller and product lost
+    ConnectionLost = 1,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_NAVIGATEHOMESTATECHANGED_REASON_LOWBATTERY = 2
+    ///
+    /// Low battery occurred
+    LowBattery = 2,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_NAVIGATEHOMESTATECHANGED_REASON_FINISHED = 3
+    ///
+    /// Navigate home is finished
+    Finished = 3,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_NAVIGATEHOMESTATECHANGED_REASON_STOPPED = 4
+    ///
+    /// Navigate home has been stopped
+    Stopped = 4,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_NAVIGATEHOMESTATECHANGED_REASON_DISABLED = 5
+    ///
+    /// Navigate home disabled by product
+    Disabled = 5,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_NAVIGATEHOMESTATECHANGED_REASON_ENABLED = 6
+    ///
+    /// Navigate home enabled by product
+    Enabled = 6,
+}
+
+/// eARCOMMANDS_ARDRONE3_PILOTINGSTATE_LANDINGSTATECHANGED_STATE
+/// u32
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum LandingState {
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_LANDINGSTATECHANGED_STATE_LINEAR = 0
+    ///
+    /// Linear landing
+    Linear = 0,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_LANDINGSTATECHANGED_STATE_SPIRAL = 1
+    ///
+    /// Spiral landing
+    Spiral = 1,
+}
+
+/// eARCOMMANDS_ARDRONE3_PILOTINGSTATE_MOVETOCHANGED_ORIENTATION_MODE
+/// u32
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum OrientationMode {
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_MOVETOCHANGED_ORIENTATION_MODE_NONE = 0
+    ///
+    /// The drone won't change its orientation
+    None = 0,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_MOVETOCHANGED_ORIENTATION_MODE_TO_TARGET = 1
+    ///
+    /// The drone will make a rotation to look in direction of the given location
+    ToTarget = 1,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_MOVETOCHANGED_ORIENTATION_MODE_HEADING_START = 2
+    ///
+    /// The drone will orientate itself to the given heading before moving to the location
+    HeadingStart = 2,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_MOVETOCHANGED_ORIENTATION_MODE_HEADING_DURING = 3
+    ///
+    /// The drone will orientate itself to the given heading while moving to the location
+    HeadingDuring = 3,
+}
+
+/// eARCOMMANDS_ARDRONE3_PILOTINGSTATE_MOVETOCHANGED_STATUS
+/// u32
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MoveToStatus {
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_MOVETOCHANGED_STATUS_RUNNING = 0
+    ///
+    /// The drone is actually flying to the given position
+    Running = 0,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_MOVETOCHANGED_STATUS_DONE = 1
+    ///
+    /// The drone has reached the target
+    Done = 1,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_MOVETOCHANGED_STATUS_CANCELED = 2
+    ///
+    /// The move to has been canceled, either by a new moveTo command or by a CancelMoveTo command
+    Canceled = 2,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_MOVETOCHANGED_STATUS_ERROR = 3
+    ///
+    /// The move to has not been finished or started because of an error
+    Error = 3,
+}
+
+/// eARCOMMANDS_ARDRONE3_PILOTINGSTATE_MOTIONSTATE_STATE
+/// u32
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Motion {
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_MOTIONSTATE_STATE_STEADY = 0
+    ///
+    /// Drone is steady
+    Steady = 0,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_MOTIONSTATE_STATE_MOVING = 1
+    ///
+    /// Drone is moving
+    Moving = 1,
+}
+
+/// eARCOMMANDS_ARDRONE3_PILOTINGSTATE_PILOTEDPOI_STATUS
+/// u32
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PilotedPOIStatus {
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_PILOTEDPOI_STATUS_UNAVAILABLE = 0
+    ///
+    /// The piloted POI is not available
+    Unavailable = 0,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_PILOTEDPOI_STATUS_AVAILABLE = 1
+    ///
+    /// The piloted POI is available
+    Available = 1,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_PILOTEDPOI_STATUS_PENDING = 2
+    ///
+    /// Piloted POI has been requested. Waiting to be in state that allow the piloted POI to start
+    Pending = 2,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_PILOTEDPOI_STATUS_RUNNING = 3
+    ///
+    /// Piloted POI is running
+    Running = 3,
+}
+
+/// eARCOMMANDS_ARDRONE3_PILOTINGSTATE_RETURNHOMEBATTERYCAPACITY_STATUS
+/// u32
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ReturnHomeBatteryCapacityStatus {
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_RETURNHOMEBATTERYCAPACITY_STATUS_OK = 0
+    ///
+    /// The battery is full enough to do a return home
+    Ok = 0,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_RETURNHOMEBATTERYCAPACITY_STATUS_WARNING = 1
+    ///
+    /// The battery is about to be too discharged to do a return home
+    Warning = 1,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_RETURNHOMEBATTERYCAPACITY_STATUS_CRITICAL = 2
+    ///
+    /// The battery level is too low to return to the home position
+    Critical = 2,
+    /// ARCOMMANDS_ARDRONE3_PILOTINGSTATE_RETURNHOMEBATTERYCAPACITY_STATUS_UNKNOWN = 3
+    ///
+    /// Battery capacity to do a return home is unknown. This can be either because the home is unknown or the position of the drone is unknown, or the drone has not enough information to determine how long it takes to fly home.
+    Unknown = 3,
+}
+
+impl From<&PilotingState> for u16 {
+    fn from(piloting_state: &PilotingState) -> Self {
         use PilotingState::*;
 
-        match self {
+        match piloting_state {
             FlatTrimChanged => 0,
-            FlyingStateChanged => 1,
-            AlterStateChanged => 2,
-            NavigateHomeStateChanged => 3,
-            PositionChanged => 4,
-            SpeedChanged => 5,
-            AttitudeChanged => 6,
-            AutoTakeOffModeChanged => 7,
-            AltitudeChanged => 8,
-            GpsLocationChanged => 9,
-            LandingStateChanged => 10,
-            AirspeedChanged => 11,
-            MoveToChanged => 12,
-            MotionState => 13,
-            PilotedPOI => 14,
-            ReturnHomeBatteryCapacity => 15,
+            FlyingStateChanged(_) => 1,
+            AlertStateChanged(_) => 2,
+            NavigateHomeStateChanged { .. } => 3,
+            PositionChanged { .. } => 4,
+            SpeedChanged { .. } => 5,
+            AttitudeChanged { .. } => 6,
+            AutoTakeOffModeChanged(_) => 7,
+            AltitudeChanged(_) => 8,
+            GpsLocationChanged { .. } => 9,
+            LandingStateChanged(_) => 10,
+            AirspeedChanged(_) => 11,
+            MoveToChanged { .. } => 12,
+            MotionState(_) => 13,
+            PilotedPOI { .. } => 14,
+            ReturnHomeBatteryCapacity(_) => 15,
             Unknown { piloting_state, .. } => *piloting_state,
         }
     }
@@ -118,8 +414,57 @@ pub mod scroll_impl {
         fn try_from_ctx(src: &'a [u8], ctx: Endian) -> Result<(Self, usize), Self::Error> {
             let mut offset = 0;
 
-            #[allow(clippy::match_single_binding)]
             let piloting_state = match src.gread_with::<u16>(&mut offset, ctx)? {
+                0 => Self::FlatTrimChanged,
+                1 => Self::FlyingStateChanged(src.gread_with(&mut offset, ctx)?),
+                2 => Self::AlertStateChanged(src.gread_with(&mut offset, ctx)?),
+                3 => Self::NavigateHomeStateChanged {
+                    state: src.gread_with(&mut offset, ctx)?,
+                    reason: src.gread_with(&mut offset, ctx)?,
+                },
+                4 => Self::PositionChanged {
+                    latitude: src.gread_with(&mut offset, ctx)?,
+                    longitude: src.gread_with(&mut offset, ctx)?,
+                    altitude: src.gread_with(&mut offset, ctx)?,
+                },
+                5 => Self::SpeedChanged {
+                    speed_x: src.gread_with(&mut offset, ctx)?,
+                    speed_y: src.gread_with(&mut offset, ctx)?,
+                    speed_z: src.gread_with(&mut offset, ctx)?,
+                },
+                6 => Self::AttitudeChanged {
+                    roll: src.gread_with(&mut offset, ctx)?,
+                    pitch: src.gread_with(&mut offset, ctx)?,
+                    yaw: src.gread_with(&mut offset, ctx)?,
+                },
+                7 => Self::AutoTakeOffModeChanged(src.gread_with::<u8>(&mut offset, ctx)? == 1),
+                8 => Self::AltitudeChanged(src.gread_with(&mut offset, ctx)?),
+                9 => Self::GpsLocationChanged {
+                    latitude: src.gread_with(&mut offset, ctx)?,
+                    longitude: src.gread_with(&mut offset, ctx)?,
+                    altitude: src.gread_with(&mut offset, ctx)?,
+                    latitude_accuracy: src.gread_with(&mut offset, ctx)?,
+                    longitude_accuracy: src.gread_with(&mut offset, ctx)?,
+                    altitude_accuracy: src.gread_with(&mut offset, ctx)?,
+                },
+                10 => Self::LandingStateChanged(src.gread_with(&mut offset, ctx)?),
+                11 => Self::AirspeedChanged(src.gread_with(&mut offset, ctx)?),
+                12 => Self::MoveToChanged {
+                    latitude: src.gread_with(&mut offset, ctx)?,
+                    longitude: src.gread_with(&mut offset, ctx)?,
+                    altitude: src.gread_with(&mut offset, ctx)?,
+                    orientation_mode: src.gread_with(&mut offset, ctx)?,
+                    heading: src.gread_with(&mut offset, ctx)?,
+                    status: src.gread_with(&mut offset, ctx)?,
+                },
+                13 => Self::MotionState(src.gread_with(&mut offset, ctx)?),
+                14 => Self::PilotedPOI {
+                    latitude: src.gread_with(&mut offset, ctx)?,
+                    longitude: src.gread_with(&mut offset, ctx)?,
+                    altitude: src.gread_with(&mut offset, ctx)?,
+                    status: src.gread_with(&mut offset, ctx)?,
+                },
+                15 => Self::ReturnHomeBatteryCapacity(src.gread_with(&mut offset, ctx)?),
                 unknown => Self::Unknown {
                     piloting_state: unknown,
                     data: read_unknown(src, &mut offset)?,
@@ -130,7 +475,7 @@ pub mod scroll_impl {
         }
     }
 
-    impl<'a> ctx::TryIntoCtx<Endian> for PilotingState {
+    impl ctx::TryIntoCtx<Endian> for PilotingState {
         type Error = Error;
 
         fn try_into_ctx(self, this: &mut [u8], ctx: Endian) -> Result<usize, Self::Error> {
@@ -139,13 +484,493 @@ pub mod scroll_impl {
             this.gwrite_with::<u16>((&self).into(), &mut offset, ctx)?;
 
             match self {
+                PilotingState::FlatTrimChanged => {}
+                PilotingState::FlyingStateChanged(flying_state) => {
+                    this.gwrite_with(flying_state, &mut offset, ctx)?;
+                }
+                PilotingState::AlertStateChanged(alert_state) => {
+                    this.gwrite_with(alert_state, &mut offset, ctx)?;
+                }
+                PilotingState::NavigateHomeStateChanged { state, reason } => {
+                    this.gwrite_with(state, &mut offset, ctx)?;
+                    this.gwrite_with(reason, &mut offset, ctx)?;
+                }
+                PilotingState::PositionChanged {
+                    latitude,
+                    longitude,
+                    altitude,
+                } => {
+                    this.gwrite_with(latitude, &mut offset, ctx)?;
+                    this.gwrite_with(longitude, &mut offset, ctx)?;
+                    this.gwrite_with(altitude, &mut offset, ctx)?;
+                }
+                PilotingState::SpeedChanged {
+                    speed_x,
+                    speed_y,
+                    speed_z,
+                } => {
+                    this.gwrite_with(speed_x, &mut offset, ctx)?;
+                    this.gwrite_with(speed_y, &mut offset, ctx)?;
+                    this.gwrite_with(speed_z, &mut offset, ctx)?;
+                }
+                PilotingState::AttitudeChanged { roll, pitch, yaw } => {
+                    this.gwrite_with(roll, &mut offset, ctx)?;
+                    this.gwrite_with(pitch, &mut offset, ctx)?;
+                    this.gwrite_with(yaw, &mut offset, ctx)?;
+                }
+                PilotingState::AutoTakeOffModeChanged(enabled) => {
+                    this.gwrite_with::<u8>(enabled.into(), &mut offset, ctx)?;
+                }
+                PilotingState::AltitudeChanged(altitude) => {
+                    this.gwrite_with(altitude, &mut offset, ctx)?;
+                }
+                PilotingState::GpsLocationChanged {
+                    latitude,
+                    longitude,
+                    altitude,
+                    latitude_accuracy,
+                    longitude_accuracy,
+                    altitude_accuracy,
+                } => {
+                    this.gwrite_with(latitude, &mut offset, ctx)?;
+                    this.gwrite_with(longitude, &mut offset, ctx)?;
+                    this.gwrite_with(altitude, &mut offset, ctx)?;
+                    this.gwrite_with(latitude_accuracy, &mut offset, ctx)?;
+                    this.gwrite_with(longitude_accuracy, &mut offset, ctx)?;
+                    this.gwrite_with(altitude_accuracy, &mut offset, ctx)?;
+                }
+                PilotingState::LandingStateChanged(landing_state) => {
+                    this.gwrite_with(landing_state, &mut offset, ctx)?;
+                }
+                PilotingState::AirspeedChanged(airspeed) => {
+                    this.gwrite_with(airspeed, &mut offset, ctx)?;
+                }
+                PilotingState::MoveToChanged {
+                    latitude,
+                    longitude,
+                    altitude,
+                    orientation_mode,
+                    heading,
+                    status,
+                } => {
+                    this.gwrite_with(latitude, &mut offset, ctx)?;
+                    this.gwrite_with(longitude, &mut offset, ctx)?;
+                    this.gwrite_with(altitude, &mut offset, ctx)?;
+                    this.gwrite_with(orientation_mode, &mut offset, ctx)?;
+                    this.gwrite_with(heading, &mut offset, ctx)?;
+                    this.gwrite_with(status, &mut offset, ctx)?;
+                }
+                PilotingState::MotionState(motion) => {
+                    this.gwrite_with(motion, &mut offset, ctx)?;
+                }
+                PilotingState::PilotedPOI {
+                    latitude,
+                    longitude,
+                    altitude,
+                    status,
+                } => {
+                    this.gwrite_with(latitude, &mut offset, ctx)?;
+                    this.gwrite_with(longitude, &mut offset, ctx)?;
+                    this.gwrite_with(altitude, &mut offset, ctx)?;
+                    this.gwrite_with(status, &mut offset, ctx)?;
+                }
+                PilotingState::ReturnHomeBatteryCapacity(status) => {
+                    this.gwrite_with(status, &mut offset, ctx)?;
+                }
                 PilotingState::Unknown { data, .. } => {
                     this.gwrite_with(data.as_slice(), &mut offset, ())?;
                 }
-                _ => unimplemented!("Not all PilotingState are impled!"),
             }
 
             Ok(offset)
         }
     }
+
+    impl<'a> ctx::TryFromCtx<'a, Endian> for FlyingState {
+        type Error = Error;
+
+        fn try_from_ctx(src: &'a [u8], ctx: Endian) -> Result<(Self, usize), Self::Error> {
+            let mut offset = 0;
+
+            let flying_state = match src.gread_with::<u32>(&mut offset, ctx)? {
+                0 => Self::Landed,
+                1 => Self::TakingOff,
+                2 => Self::Hovering,
+                3 => Self::Flying,
+                4 => Self::Landing,
+                5 => Self::Emergency,
+                6 => Self::UserTakeOff,
+                7 => Self::MotorRamping,
+                8 => Self::EmergencyLanding,
+                value => {
+                    return Err(Error::OutOfBound {
+                        value: value.into(),
+                        param: "FlyingState".to_string(),
+                    })
+                }
+            };
+
+            Ok((flying_state, offset))
+        }
+    }
+
+    impl ctx::TryIntoCtx<Endian> for FlyingState {
+        type Error = Error;
+
+        fn try_into_ctx(self, this: &mut [u8], ctx: Endian) -> Result<usize, Self::Error> {
+            Ok(this.pwrite_with::<u32>(self as u32, 0, ctx)?)
+        }
+    }
+
+    impl<'a> ctx::TryFromCtx<'a, Endian> for AlertState {
+        type Error = Error;
+
+        fn try_from_ctx(src: &'a [u8], ctx: Endian) -> Result<(Self, usize), Self::Error> {
+            let mut offset = 0;
+
+            let alert_state = match src.gread_with::<u32>(&mut offset, ctx)? {
+                0 => Self::None,
+                1 => Self::User,
+                2 => Self::CutOut,
+                3 => Self::CriticalBattery,
+                4 => Self::LowBattery,
+                5 => Self::TooMuchAngle,
+                6 => Self::AlmostEmptyBattery,
+                7 => Self::MagnetoPertubation,
+                8 => Self::MagnetoLowEarthField,
+                value => {
+                    return Err(Error::OutOfBound {
+                        value: value.into(),
+                        param: "AlertState".to_string(),
+                    })
+                }
+            };
+
+            Ok((alert_state, offset))
+        }
+    }
+
+    impl ctx::TryIntoCtx<Endian> for AlertState {
+        type Error = Error;
+
+        fn try_into_ctx(self, this: &mut [u8], ctx: Endian) -> Result<usize, Self::Error> {
+            Ok(this.pwrite_with::<u32>(self as u32, 0, ctx)?)
+        }
+    }
+
+    impl<'a> ctx::TryFromCtx<'a, Endian> for NavigateHomeState {
+        type Error = Error;
+
+        fn try_from_ctx(src: &'a [u8], ctx: Endian) -> Result<(Self, usize), Self::Error> {
+            let mut offset = 0;
+
+            let navigate_home_state = match src.gread_with::<u32>(&mut offset, ctx)? {
+                0 => Self::Available,
+                1 => Self::InProgress,
+                2 => Self::Unavailable,
+                3 => Self::Pending,
+                value => {
+                    return Err(Error::OutOfBound {
+                        value: value.into(),
+                        param: "NavigateHomeState".to_string(),
+                    })
+                }
+            };
+
+            Ok((navigate_home_state, offset))
+        }
+    }
+
+    impl ctx::TryIntoCtx<Endian> for NavigateHomeState {
+        type Error = Error;
+
+        fn try_into_ctx(self, this: &mut [u8], ctx: Endian) -> Result<usize, Self::Error> {
+            Ok(this.pwrite_with::<u32>(self as u32, 0, ctx)?)
+        }
+    }
+
+    impl<'a> ctx::TryFromCtx<'a, Endian> for NavigateHomeReason {
+        type Error = Error;
+
+        fn try_from_ctx(src: &'a [u8], ctx: Endian) -> Result<(Self, usize), Self::Error> {
+            let mut offset = 0;
+
+            let navigate_home_reason = match src.gread_with::<u32>(&mut offset, ctx)? {
+                0 => Self::UserRequest,
+                1 => Self::ConnectionLost,
+                2 => Self::LowBattery,
+                3 => Self::Finished,
+                4 => Self::Stopped,
+                5 => Self::Disabled,
+                6 => Self::Enabled,
+                value => {
+                    return Err(Error::OutOfBound {
+                        value: value.into(),
+                        param: "NavigateHomeReason".to_string(),
+                    })
+                }
+            };
+
+            Ok((navigate_home_reason, offset))
+        }
+    }
+
+    impl ctx::TryIntoCtx<Endian> for NavigateHomeReason {
+        type Error = Error;
+
+        fn try_into_ctx(self, this: &mut [u8], ctx: Endian) -> Result<usize, Self::Error> {
+            Ok(this.pwrite_with::<u32>(self as u32, 0, ctx)?)
+        }
+    }
+
+    impl<'a> ctx::TryFromCtx<'a, Endian> for LandingState {
+        type Error = Error;
+
+        fn try_from_ctx(src: &'a [u8], ctx: Endian) -> Result<(Self, usize), Self::Error> {
+            let mut offset = 0;
+
+            let landing_state = match src.gread_with::<u32>(&mut offset, ctx)? {
+                0 => Self::Linear,
+                1 => Self::Spiral,
+                value => {
+                    return Err(Error::OutOfBound {
+                        value: value.into(),
+                        param: "LandingState".to_string(),
+                    })
+                }
+            };
+
+            Ok((landing_state, offset))
+        }
+    }
+
+    impl ctx::TryIntoCtx<Endian> for LandingState {
+        type Error = Error;
+
+        fn try_into_ctx(self, this: &mut [u8], ctx: Endian) -> Result<usize, Self::Error> {
+            Ok(this.pwrite_with::<u32>(self as u32, 0, ctx)?)
+        }
+    }
+
+    impl<'a> ctx::TryFromCtx<'a, Endian> for OrientationMode {
+        type Error = Error;
+
+        fn try_from_ctx(src: &'a [u8], ctx: Endian) -> Result<(Self, usize), Self::Error> {
+            let mut offset = 0;
+
+            let orientation_mode = match src.gread_with::<u32>(&mut offset, ctx)? {
+                0 => Self::None,
+                1 => Self::ToTarget,
+                2 => Self::HeadingStart,
+                3 => Self::HeadingDuring,
+                value => {
+                    return Err(Error::OutOfBound {
+                        value: value.into(),
+                        param: "OrientationMode".to_string(),
+                    })
+                }
+            };
+
+            Ok((orientation_mode, offset))
+        }
+    }
+
+    impl ctx::TryIntoCtx<Endian> for OrientationMode {
+        type Error = Error;
+
+        fn try_into_ctx(self, this: &mut [u8], ctx: Endian) -> Result<usize, Self::Error> {
+            Ok(this.pwrite_with::<u32>(self as u32, 0, ctx)?)
+        }
+    }
+
+    impl<'a> ctx::TryFromCtx<'a, Endian> for MoveToStatus {
+        type Error = Error;
+
+        // `Self::Error` is ambiguous with `MoveToStatus::Error`
+
+        fn try_from_ctx(src: &'a [u8], ctx: Endian) -> Result<(Self, usize), Error> {
+            let mut offset = 0;
+
+            let move_to_status = match src.gread_with::<u32>(&mut offset, ctx)? {
+                0 => Self::Running,
+                1 => Self::Done,
+                2 => Self::Canceled,
+                3 => Self::Error,
+                value => {
+                    return Err(Error::OutOfBound {
+                        value: value.into(),
+                        param: "MoveToStatus".to_string(),
+                    })
+                }
+            };
+
+            Ok((move_to_status, offset))
+        }
+    }
+
+    impl ctx::TryIntoCtx<Endian> for MoveToStatus {
+        type Error = Error;
+
+        // `Self::Error` is ambiguous with `MoveToStatus::Error`
+
+        fn try_into_ctx(self, this: &mut [u8], ctx: Endian) -> Result<usize, Error> {
+            Ok(this.pwrite_with::<u32>(self as u32, 0, ctx)?)
+        }
+    }
+
+    impl<'a> ctx::TryFromCtx<'a, Endian> for Motion {
+        type Error = Error;
+
+        fn try_from_ctx(src: &'a [u8], ctx: Endian) -> Result<(Self, usize), Self::Error> {
+            let mut offset = 0;
+
+            let motion = match src.gread_with::<u32>(&mut offset, ctx)? {
+                0 => Self::Steady,
+                1 => Self::Moving,
+                value => {
+                    return Err(Error::OutOfBound {
+                        value: value.into(),
+                        param: "Motion".to_string(),
+                    })
+                }
+            };
+
+            Ok((motion, offset))
+        }
+    }
+
+    impl ctx::TryIntoCtx<Endian> for Motion {
+        type Error = Error;
+
+        fn try_into_ctx(self, this: &mut [u8], ctx: Endian) -> Result<usize, Self::Error> {
+            Ok(this.pwrite_with::<u32>(self as u32, 0, ctx)?)
+        }
+    }
+
+    impl<'a> ctx::TryFromCtx<'a, Endian> for PilotedPOIStatus {
+        type Error = Error;
+
+        fn try_from_ctx(src: &'a [u8], ctx: Endian) -> Result<(Self, usize), Self::Error> {
+            let mut offset = 0;
+
+            let piloted_poi_status = match src.gread_with::<u32>(&mut offset, ctx)? {
+                0 => Self::Unavailable,
+                1 => Self::Available,
+                2 => Self::Pending,
+                3 => Self::Running,
+                value => {
+                    return Err(Error::OutOfBound {
+                        value: value.into(),
+                        param: "PilotedPOIStatus".to_string(),
+                    })
+                }
+            };
+
+            Ok((piloted_poi_status, offset))
+        }
+    }
+
+    impl ctx::TryIntoCtx<Endian> for PilotedPOIStatus {
+        type Error = Error;
+
+        fn try_into_ctx(self, this: &mut [u8], ctx: Endian) -> Result<usize, Self::Error> {
+            Ok(this.pwrite_with::<u32>(self as u32, 0, ctx)?)
+        }
+    }
+
+    impl<'a> ctx::TryFromCtx<'a, Endian> for ReturnHomeBatteryCapacityStatus {
+        type Error = Error;
+
+        fn try_from_ctx(src: &'a [u8], ctx: Endian) -> Result<(Self, usize), Self::Error> {
+            let mut offset = 0;
+
+            let return_home_battery_capacity_status =
+                match src.gread_with::<u32>(&mut offset, ctx)? {
+                    0 => Self::Ok,
+                    1 => Self::Warning,
+                    2 => Self::Critical,
+                    3 => Self::Unknown,
+                    value => {
+                        return Err(Error::OutOfBound {
+                            value: value.into(),
+                            param: "ReturnHomeBatteryCapacityStatus".to_string(),
+                        })
+                    }
+                };
+
+            Ok((return_home_battery_capacity_status, offset))
+        }
+    }
+
+    impl ctx::TryIntoCtx<Endian> for ReturnHomeBatteryCapacityStatus {
+        type Error = Error;
+
+        fn try_into_ctx(self, this: &mut [u8], ctx: Endian) -> Result<usize, Self::Error> {
+            Ok(this.pwrite_with::<u32>(self as u32, 0, ctx)?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scroll::{Pread, Pwrite, LE};
+
+    fn assert_round_trip(piloting_state: PilotingState, expected_bytes: &[u8]) {
+        let mut buf = [0_u8; 64];
+        let written = buf
+            .pwrite_with(piloting_state.clone(), 0, LE)
+            .expect("Should serialize PilotingState");
+
+        assert_eq!(expected_bytes, &buf[..written]);
+        assert_eq!(
+            piloting_state,
+            expected_bytes
+                .pread_with::<PilotingState>(0, LE)
+                .expect("Should deserialize PilotingState")
+        );
+    }
+
+    #[test]
+    fn test_piloting_state() {
+        assert_round_trip(
+            PilotingState::FlyingStateChanged(FlyingState::MotorRamping),
+            &[1, 0, 7, 0, 0, 0],
+        );
+        assert_round_trip(
+            PilotingState::NavigateHomeStateChanged {
+                state: NavigateHomeState::InProgress,
+                reason: NavigateHomeReason::LowBattery,
+            },
+            &[3, 0, 1, 0, 0, 0, 2, 0, 0, 0],
+        );
+        assert_round_trip(PilotingState::AutoTakeOffModeChanged(true), &[7, 0, 1]);
+        assert_round_trip(
+            PilotingState::SpeedChanged {
+                speed_x: 0.0,
+                speed_y: -0.0,
+                speed_z: 0.0,
+            },
+            &[5, 0, 0, 0, 0, 0, 0, 0, 0, 128, 0, 0, 0, 0],
+        );
+        assert_round_trip(
+            PilotingState::PilotedPOI {
+                latitude: 500.0,
+                longitude: 500.0,
+                altitude: 500.0,
+                status: PilotedPOIStatus::Running,
+            },
+            &[
+                14, 0, 0, 0, 0, 0, 0, 64, 127, 64, 0, 0, 0, 0, 0, 64, 127, 64, 0, 0, 0, 0, 0, 64,
+                127, 64, 3, 0, 0, 0,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_out_of_bound_enum() {
+        let bytes: [u8; 6] = [13, 0, 2, 0, 0, 0];
+
+        assert!(bytes.pread_with::<PilotingState>(0, LE).is_err());
+    }
 }
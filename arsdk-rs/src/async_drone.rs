@@ -0,0 +1,460 @@
+//! `tokio` based Drone, enabled with the `tokio` feature.
+//!
+//! The Handshake, Listener, Command sender and the re-sender of the not acknowledged
+//! `Type::DataWithAck` frames are all running as `tokio` tasks instead of OS threads.
+//!
+//! **NOTE:** The `LinkState` is tracked, but `Config::reconnect` is not supported yet.
+//!
+//! ```ignore
+//! use arsdk_rs::{prelude::*, subscription::Filter, AsyncDrone};
+//! use tokio::stream::StreamExt;
+//!
+//! let drone = AsyncDrone::connect(PARROT_SPHINX_CONFIG).await?;
+//! let mut piloting_states = drone.subscribe(Filter::Class { feature: 1, class: 4 });
+//!
+//! drone.send_frame(take_off_frame).await?.await?;
+//!
+//! while let Some(frame) = piloting_states.next().await {
+//!     println!("{:?}", frame);
+//! }
+//! ```
+use crate::{
+    handshake::perform_handshake_async,
+    link::{self, LinkState, LinkStats},
+    local_ip,
+    parse::handle_bytes,
+    print_buf,
+    record::{record, Direction},
+    state::DroneState,
+    subscription::{self, Filter, FrameStream},
+    AckHandle, CommandSender, Config, ConnectionError, Drone, Error, WeakDrone, COMMANDS_CAPACITY,
+    SHUTDOWN_POLL_INTERVAL,
+};
+use chrono::Utc;
+use log::{debug, error, info, warn};
+use scroll::{Pread, LE};
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::{mpsc, Arc, Mutex, PoisonError},
+    time::Instant,
+};
+use tokio::{
+    net::UdpSocket,
+    sync::mpsc::{channel, Receiver},
+    task::JoinHandle,
+    time::timeout,
+};
+
+/// Same as the blocking `Drone`, but the sending of frames can be awaited
+/// and the incoming frames are received as a `Stream`.
+///
+/// **NOTE:** It should be connected from inside a `tokio` runtime.
+#[derive(Clone, Debug)]
+pub struct AsyncDrone {
+    drone: Drone,
+    tasks: Arc<Mutex<Vec<JoinHandle<()>>>>,
+}
+
+impl AsyncDrone {
+    /// Connects to a drone
+    ///
+    /// * Spawns Listener task at `Config::listen_port`
+    /// * Performs Handshake at `Config::init_port`
+    /// * Spawns Command sender task at `c2d_port`
+    /// * Spawns the re-sender task of the not acknowledged `Type::DataWithAck` frames
+    /// * Spawns the Link monitor task, which updates the `LinkState` and PINGs the Drone
+    ///
+    /// The tasks are stopped on `AsyncDrone::disconnect()` or shortly after
+    /// the last clone of the `AsyncDrone` is dropped.
+    pub async fn connect(config: Config) -> Result<Self, ConnectionError> {
+        let local_ip =
+            local_ip(&config).ok_or_else(|| ConnectionError::DroneAddr(config.drone_addr))?;
+
+        let (tx_cmd, rx_cmd) = channel(COMMANDS_CAPACITY);
+
+        let drone = Self {
+            drone: Drone::new(CommandSender::Tokio(tx_cmd), &config),
+            tasks: Default::default(),
+        };
+
+        let local_listener = SocketAddr::new(local_ip, config.listen_port);
+        info!("{}: Spawning Listener", &local_listener);
+
+        let (listener, d2c_port) = spawn_listener(&drone.drone, local_listener).await?;
+        drone.add_task(listener);
+
+        let handshake_response = perform_handshake_async(&config, d2c_port).await?;
+        let cmd_sender_target = SocketAddr::new(config.drone_addr, handshake_response.c2d_port);
+
+        info!("{}: Spawning CMD Sender", cmd_sender_target);
+
+        drone.add_task(spawn_cmd_sender(&drone.drone, rx_cmd, local_ip, cmd_sender_target).await?);
+
+        drone.add_task(spawn_ack_retransmitter(&drone.drone));
+        drone.add_task(spawn_link_monitor(&drone.drone));
+
+        if config.send_datetime {
+            drone.drone.send_datetime(Utc::now())?;
+        }
+
+        Ok(drone)
+    }
+
+    /// The underlying `Drone`, e.g. for `Frame::for_drone`
+    pub fn drone(&self) -> &Drone {
+        &self.drone
+    }
+
+    /// Sends the frame, waiting only if the channel of the Command sender is full.
+    ///
+    /// `Type::DataWithAck` frames are re-sent until the Drone acknowledges them,
+    /// await the returned `AckHandle` for the Ack.
+    pub async fn send_frame(&self, frame: crate::frame::Frame) -> Result<AckHandle, Error> {
+        let (handle, raw_message) = self.drone.prepare_frame(frame)?;
+
+        if let Err(err) = self.send_raw_message(raw_message).await {
+            self.drone.forget_frame(&handle);
+
+            return Err(err);
+        }
+
+        Ok(handle)
+    }
+
+    pub async fn send_raw_message(&self, raw_message: Vec<u8>) -> Result<(), Error> {
+        self.drone.inner.sender.send_async(raw_message).await
+    }
+
+    /// Stops the Listener, Command sender and the re-sender tasks and waits for them to finish,
+    /// which closes the UDP sockets and frees the listener port.
+    ///
+    /// See `Drone::disconnect()`
+    pub async fn disconnect(&self) {
+        self.drone.disconnect();
+
+        let tasks = std::mem::take(&mut *self.tasks.lock().unwrap_or_else(PoisonError::into_inner));
+
+        for task in tasks {
+            if let Err(err) = task.await {
+                error!("Background task of the Drone failed: {}", err);
+            }
+        }
+    }
+
+    pub fn is_disconnected(&self) -> bool {
+        self.drone.is_disconnected()
+    }
+
+    /// Snapshot of the latest known state of the Drone
+    pub fn state(&self) -> DroneState {
+        self.drone.state()
+    }
+
+    /// See `Drone::link_state()`
+    pub fn link_state(&self) -> LinkState {
+        self.drone.link_state()
+    }
+
+    /// See `Drone::link_events()`
+    pub fn link_events(&self) -> mpsc::Receiver<LinkState> {
+        self.drone.link_events()
+    }
+
+    /// See `Drone::link_stats()`
+    pub fn link_stats(&self) -> LinkStats {
+        self.drone.link_stats()
+    }
+
+    /// A `Stream` of the incoming frames that match the `Filter`.
+    ///
+    /// Up to `subscription::DEFAULT_CAPACITY` frames are buffered,
+    /// after that the newest frames are dropped until the `FrameStream` catches up.
+    pub fn subscribe(&self, filter: Filter) -> FrameStream {
+        self.subscribe_with(filter, subscription::DEFAULT_CAPACITY)
+    }
+
+    /// A `Stream` of the incoming frames that match the `Filter` with a custom buffer `capacity`,
+    /// which is at least `subscription::MIN_CAPACITY`.
+    pub fn subscribe_with(&self, filter: Filter, capacity: usize) -> FrameStream {
+        self.drone
+            .inner
+            .subscribers
+            .subscribe_stream(filter, capacity)
+    }
+
+    fn add_task(&self, task: JoinHandle<()>) {
+        self.tasks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(task);
+    }
+}
+
+/// Returns the bound port too, e.g. the free port that was picked for port `0`
+async fn spawn_listener(
+    drone: &Drone,
+    addr: SocketAddr,
+) -> Result<(JoinHandle<()>, u16), ConnectionError> {
+    let mut socket = UdpSocket::bind(addr)
+        .await
+        .map_err(|error| ConnectionError::Io { error, addr })?;
+    let d2c_port = socket
+        .local_addr()
+        .map_err(|error| ConnectionError::Io { error, addr })?
+        .port();
+    let weak_drone = drone.downgrade();
+
+    let listener = tokio::spawn(async move {
+        let mut buf = [0_u8; 256];
+
+        loop {
+            match timeout(SHUTDOWN_POLL_INTERVAL, socket.recv_from(&mut buf)).await {
+                Ok(Ok((bytes_read, origin))) => {
+                    debug!("Received: {} bytes from {}", bytes_read, origin);
+                    debug!("Bytes: {}", print_buf(&buf[..bytes_read]));
+
+                    let drone = match weak_drone.upgrade() {
+                        Some(drone) => drone,
+                        None => break,
+                    };
+                    let raw_frames = buf[..bytes_read].to_vec();
+
+                    // a `Policy::Block` subscriber can block the handling of the frames,
+                    // so keep it off the runtime
+                    let handled = tokio::task::spawn_blocking(move || {
+                        record(
+                            drone.inner.recorder.as_deref(),
+                            Direction::Incoming,
+                            &raw_frames,
+                        );
+                        handle_bytes(&drone, &raw_frames)
+                    })
+                    .await;
+
+                    if let Err(err) = handled {
+                        error!("Handling the received bytes: {}", err);
+                    }
+                }
+                Ok(Err(err)) => error!("Receiving from Drone: {}", err),
+                Err(_elapsed) if weak_drone.upgrade().is_none() => break,
+                Err(_elapsed) => {}
+            }
+        }
+
+        debug!("Listener stopped");
+    });
+
+    Ok((listener, d2c_port))
+}
+
+async fn spawn_cmd_sender(
+    drone: &Drone,
+    mut rx: Receiver<Vec<u8>>,
+    local_ip: IpAddr,
+    target_addr: SocketAddr,
+) -> Result<JoinHandle<()>, ConnectionError> {
+    // any free port, so multiple Drones with the same `c2d_port` can be connected
+    let local_addr = SocketAddr::new(local_ip, 0);
+
+    let mut socket = UdpSocket::bind(local_addr)
+        .await
+        .map_err(|error| ConnectionError::Io {
+            error,
+            addr: local_addr,
+        })?;
+    let weak_drone = drone.downgrade();
+    let recorder = drone.inner.recorder.clone();
+
+    Ok(tokio::spawn(async move {
+        loop {
+            let received = timeout(SHUTDOWN_POLL_INTERVAL, rx.recv()).await;
+
+            // checked before every frame too, or a steady stream of frames would keep us running
+            // after the Drone was disconnected or dropped
+            if weak_drone.upgrade().is_none() {
+                break;
+            }
+
+            let frame_to_send = match received {
+                Ok(Some(frame)) => frame,
+                Err(_elapsed) => continue,
+                Ok(None) => break,
+            };
+
+            let frame = frame_to_send.pread_with::<crate::frame::Frame>(0, LE);
+
+            info!(
+                "Sent Frame (length: {}) => {:?}",
+                frame_to_send.len(),
+                &frame
+            );
+
+            match socket.send_to(&frame_to_send, target_addr).await {
+                Ok(_) => record(recorder.as_deref(), Direction::Outgoing, &frame_to_send),
+                Err(err) => error!("Sending Frame to Drone: {}", err),
+            }
+        }
+    }))
+}
+
+fn spawn_ack_retransmitter(drone: &Drone) -> JoinHandle<()> {
+    let mut interval = tokio::time::interval(drone.inner.pending_acks.check_interval());
+    let weak_drone: WeakDrone = drone.downgrade();
+
+    tokio::spawn(async move {
+        loop {
+            interval.tick().await;
+
+            let drone = match weak_drone.upgrade() {
+                Some(drone) => drone,
+                None => break,
+            };
+
+            for raw_message in drone.inner.pending_acks.retransmissions(Instant::now()) {
+                info!("Re-sending Frame: {}", print_buf(&raw_message));
+
+                if let Err(err) = drone.inner.sender.send_async(raw_message).await {
+                    error!("Re-sending Frame to Commander: {}", err)
+                }
+            }
+        }
+    })
+}
+
+fn spawn_link_monitor(drone: &Drone) -> JoinHandle<()> {
+    let mut interval = tokio::time::interval(SHUTDOWN_POLL_INTERVAL);
+    let weak_drone = drone.downgrade();
+    let mut last_ping = Instant::now();
+
+    tokio::spawn(async move {
+        loop {
+            interval.tick().await;
+
+            let drone = match weak_drone.upgrade() {
+                Some(drone) => drone,
+                None => break,
+            };
+
+            let now = Instant::now();
+            match drone.inner.link.update(now) {
+                Some(LinkState::Lost) => warn!("Link to the Drone is lost"),
+                Some(link_state) => info!("Link to the Drone is {:?}", link_state),
+                None => {}
+            }
+
+            if now.saturating_duration_since(last_ping) >= link::PING_INTERVAL {
+                last_ping = now;
+
+                if let Err(err) = drone.send_ping() {
+                    error!("Sending PING to Commander: {}", err)
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        ardrone3::{ArDrone3, FlyingState, Piloting, PilotingState},
+        command::Feature,
+        frame::{BufferID, Frame, FrameType, Type},
+        mock::MockDrone,
+    };
+    use std::{net::Ipv4Addr, time::Duration};
+    use tokio::stream::StreamExt;
+
+    fn config(mock: &MockDrone) -> Config {
+        Config::builder(Ipv4Addr::LOCALHOST)
+            .init_port(mock.init_port())
+            // any free port, the Listener is bound before the Handshake
+            .listen_port(0)
+            .handshake_retries(1)
+            .build()
+            .expect("Should be a valid Config")
+    }
+
+    #[tokio::test]
+    async fn test_connect_to_mock_drone() {
+        let mock = MockDrone::builder()
+            .start()
+            .expect("Should start the MockDrone");
+
+        let drone = AsyncDrone::connect(config(&mock))
+            .await
+            .expect("Should connect to the MockDrone");
+        assert!(mock.is_connected());
+        assert!(!drone.is_disconnected());
+
+        drone.disconnect().await;
+        assert!(drone.is_disconnected());
+    }
+
+    #[tokio::test]
+    async fn test_acknowledged_frame() {
+        let mock = MockDrone::builder()
+            .start()
+            .expect("Should start the MockDrone");
+
+        let drone = AsyncDrone::connect(config(&mock))
+            .await
+            .expect("Should connect to the MockDrone");
+
+        let take_off = Frame::for_drone(
+            drone.drone(),
+            Type::DataWithAck,
+            BufferID::CDAck,
+            Some(Feature::ArDrone3(Some(ArDrone3::Piloting(
+                Piloting::TakeOff,
+            )))),
+        );
+        let ack = drone
+            .send_frame(take_off)
+            .await
+            .expect("Should send the frame");
+        timeout(Duration::from_secs(2), ack)
+            .await
+            .expect("Should be acknowledged in time")
+            .expect("Should be acknowledged");
+
+        assert!(mock.received_frames().iter().any(|frame| frame.feature
+            == Some(Feature::ArDrone3(Some(ArDrone3::Piloting(
+                Piloting::TakeOff
+            ))))));
+
+        drone.disconnect().await;
+    }
+
+    #[tokio::test]
+    async fn test_event_stream() {
+        let mock = MockDrone::builder()
+            .start()
+            .expect("Should start the MockDrone");
+
+        let drone = AsyncDrone::connect(config(&mock))
+            .await
+            .expect("Should connect to the MockDrone");
+        let mut piloting_states = drone.subscribe(Filter::Class {
+            feature: 1,
+            class: 4,
+        });
+
+        let hovering = Feature::ArDrone3(Some(ArDrone3::PilotingState(
+            PilotingState::FlyingStateChanged(FlyingState::Hovering),
+        )));
+        mock.send_event(hovering.clone())
+            .expect("Should send the event");
+
+        let received = timeout(Duration::from_secs(2), piloting_states.next())
+            .await
+            .expect("Should receive the event in time");
+
+        match received {
+            Some(FrameType::Known(frame)) => assert_eq!(Some(hovering), frame.feature),
+            other => panic!("Expected the Hovering event, got: {:?}", other),
+        }
+
+        drone.disconnect().await;
+    }
+}
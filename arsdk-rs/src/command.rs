@@ -1,16 +1,22 @@
 use crate::ardrone3::ArDrone3;
+use crate::battery;
+use crate::camera;
 use crate::common;
+use crate::gimbal;
 use crate::jumping_sumo;
+use crate::minidrone;
+use crate::powerup;
+use crate::skycontroller;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 /// u8
 pub enum Feature {
-    Common(Option<common::Class>),    // ARCOMMANDS_ID_FEATURE_COMMON = 0,
-    ArDrone3(Option<ArDrone3>),       // ARCOMMANDS_ID_FEATURE_ARDRONE3 = 1,
-    Minidrone,                        // ARCOMMANDS_ID_FEATURE_MINIDRONE = 2,
+    Common(Option<common::Class>), // ARCOMMANDS_ID_FEATURE_COMMON = 0,
+    ArDrone3(Option<ArDrone3>),    // ARCOMMANDS_ID_FEATURE_ARDRONE3 = 1,
+    Minidrone(Option<minidrone::Class>), // ARCOMMANDS_ID_FEATURE_MINIDRONE = 2,
     JumpingSumo(jumping_sumo::Class), // ARCOMMANDS_ID_FEATURE_JUMPINGSUMO = 3,
-    SkyController,                    // ARCOMMANDS_ID_FEATURE_SKYCONTROLLER = 4,
-    PowerUp,                          // ARCOMMANDS_ID_FEATURE_POWERUP = 8,
+    SkyController(Option<skycontroller::Class>), // ARCOMMANDS_ID_FEATURE_SKYCONTROLLER = 4,
+    PowerUp(Option<powerup::Class>), // ARCOMMANDS_ID_FEATURE_POWERUP = 8,
     /// ARCOMMANDS_ID_FEATURE_GENERIC = 133,
     ///
     /// For details on the Generic check:
@@ -19,23 +25,20 @@ pub enum Feature {
     /// 3. `ARCOMMANDS_Generic_DroneSettings_t` in `libARCommands/libARCommands/ARCOMMANDS_Types.h`
     /// 4. `ARCOMMANDS_Generic_DroneSettingsChanged_t` in `libARCommands/libARCommands/ARCOMMANDS_Types.h`
     Generic,
-    FollowMe,                         // ARCOMMANDS_ID_FEATURE_FOLLOW_ME = 134,
-    Wifi,                             // ARCOMMANDS_ID_FEATURE_WIFI = 135,
-    RC,                               // ARCOMMANDS_ID_FEATURE_RC = 136,
-    DroneManager,                     // ARCOMMANDS_ID_FEATURE_DRONE_MANAGER = 137,
-    Mapper,                           // ARCOMMANDS_ID_FEATURE_MAPPER = 138,
-    Debug,                            // ARCOMMANDS_ID_FEATURE_DEBUG = 139,
-    ControllerInfo,                   // ARCOMMANDS_ID_FEATURE_CONTROLLER_INFO = 140,
-    MapperMini,                       // ARCOMMANDS_ID_FEATURE_MAPPER_MINI = 141,
-    ThermalCam,                       // ARCOMMANDS_ID_FEATURE_THERMAL_CAM = 142,
-    Animation,                        // ARCOMMANDS_ID_FEATURE_ANIMATION = 144,
-    SequoiaCam,                       // ARCOMMANDS_ID_FEATURE_SEQUOIA_CAM = 147,
-    /// Unknown 149 from anafi4k
-    /// Frame { frame_type: Data, buffer_id: DCNavdata, sequence_id: 14, feature: Some(Unknown { feature: 149, data: [0, 3, 0, 91, 33] }) }
-    /// Unknown 148 from anafi4k
-    /// Frame { frame_type: Data, buffer_id: DCNavdata, sequence_id: 5, feature: Some(Unknown { feature: 148, data: [0, 6, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0, 223, 46, 229, 182, 0, 0, 0, 0, 0, 0, 0, 0, 222, 4, 180, 66, 0, 0, 0, 128, 0, 0, 0, 0] }) }
-    /// Unknown 143 from anafi4k
-    /// Frame { frame_type: Data, buffer_id: DCNavdata, sequence_id: 4, feature: Some(Unknown { feature: 143, data: [0, 22, 0, 0, 0, 0, 128, 63] }) }
+    FollowMe,                        // ARCOMMANDS_ID_FEATURE_FOLLOW_ME = 134,
+    Wifi,                            // ARCOMMANDS_ID_FEATURE_WIFI = 135,
+    RC,                              // ARCOMMANDS_ID_FEATURE_RC = 136,
+    DroneManager,                    // ARCOMMANDS_ID_FEATURE_DRONE_MANAGER = 137,
+    Mapper,                          // ARCOMMANDS_ID_FEATURE_MAPPER = 138,
+    Debug,                           // ARCOMMANDS_ID_FEATURE_DEBUG = 139,
+    ControllerInfo,                  // ARCOMMANDS_ID_FEATURE_CONTROLLER_INFO = 140,
+    MapperMini,                      // ARCOMMANDS_ID_FEATURE_MAPPER_MINI = 141,
+    ThermalCam,                      // ARCOMMANDS_ID_FEATURE_THERMAL_CAM = 142,
+    Camera(Option<camera::Class>),   // ARCOMMANDS_ID_FEATURE_CAMERA = 143,
+    Animation,                       // ARCOMMANDS_ID_FEATURE_ANIMATION = 144,
+    SequoiaCam,                      // ARCOMMANDS_ID_FEATURE_SEQUOIA_CAM = 147,
+    Gimbal(Option<gimbal::Class>),   // ARCOMMANDS_ID_FEATURE_GIMBAL = 148,
+    Battery(Option<battery::Class>), // ARCOMMANDS_ID_FEATURE_BATTERY = 149,
     /// Unknown 155 from anafi4k
     /// Frame { frame_type: Data, buffer_id: PING, sequence_id: 0, feature: Some(Unknown { feature: 155, data: [216, 221, 13, 0, 0, 0, 0] }) }
     /// Unknown 56
@@ -60,10 +63,10 @@ impl Into<u8> for &Feature {
         match self {
             Common(_) => 0,
             ArDrone3(_) => 1,
-            Minidrone => 2,
+            Minidrone(_) => 2,
             JumpingSumo(_) => 3,
-            SkyController => 4,
-            PowerUp => 8,
+            SkyController(_) => 4,
+            PowerUp(_) => 8,
             Generic => 133,
             FollowMe => 134,
             Wifi => 135,
@@ -74,8 +77,11 @@ impl Into<u8> for &Feature {
             ControllerInfo => 140,
             MapperMini => 141,
             ThermalCam => 142,
+            Camera(_) => 143,
             Animation => 144,
             SequoiaCam => 147,
+            Gimbal(_) => 148,
+            Battery(_) => 149,
             // Temporary Enum for storing unknown Features:
             // TODO: REMOVE!
             Unknown { feature, .. } => *feature,
@@ -83,11 +89,56 @@ impl Into<u8> for &Feature {
     }
 }
 
+impl Feature {
+    /// The id of the Feature's class, if it has one
+    pub fn class_id(&self) -> Option<u8> {
+        match self {
+            Feature::Common(class) => class.as_ref().map(Into::into),
+            Feature::ArDrone3(ardrone3) => ardrone3.as_ref().map(Into::into),
+            Feature::Minidrone(class) => class.as_ref().map(minidrone::Class::id),
+            Feature::SkyController(class) => class.as_ref().map(skycontroller::Class::id),
+            Feature::PowerUp(class) => class.as_ref().map(powerup::Class::id),
+            Feature::Camera(class) => class.as_ref().map(camera::Class::id),
+            Feature::Gimbal(class) => class.as_ref().map(gimbal::Class::id),
+            Feature::Battery(class) => class.as_ref().map(battery::Class::id),
+            Feature::JumpingSumo(class) => Some(class.into()),
+            _ => None,
+        }
+    }
+}
+
 pub mod scroll_impl {
     use super::*;
     use crate::{frame::Error, parse::read_unknown};
     use scroll::{ctx, Endian, Pread, Pwrite};
 
+    /// Reads the class of a feature generated from the XML definitions.
+    ///
+    /// An unknown class or command (or an unknown enum value) is read as `Feature::Unknown`,
+    /// like an unknown feature, since the XML of most features is only a subset.
+    fn read_known<'a, C>(
+        src: &'a [u8],
+        offset: &mut usize,
+        ctx: Endian,
+        known: impl FnOnce(Option<C>) -> Feature,
+    ) -> Result<Feature, Error>
+    where
+        C: ctx::TryFromCtx<'a, Endian, Error = Error>,
+    {
+        if src[*offset..].is_empty() {
+            return Ok(known(None));
+        }
+
+        match src.gread_with(offset, ctx) {
+            Ok(class) => Ok(known(Some(class))),
+            Err(Error::OutOfBound { .. }) => Ok(Feature::Unknown {
+                feature: src[0],
+                data: read_unknown(src, offset)?,
+            }),
+            Err(err) => Err(err),
+        }
+    }
+
     impl<'a> ctx::TryFromCtx<'a, Endian> for Feature {
         type Error = Error;
 
@@ -117,14 +168,14 @@ pub mod scroll_impl {
 
                     Self::ArDrone3(ardrone3)
                 }
-                // 2 => Self::Minidrone,
+                2 => read_known(src, &mut offset, ctx, Self::Minidrone)?,
                 3 => {
                     let js_class = src.gread_with(&mut offset, ctx)?;
 
                     Self::JumpingSumo(js_class)
                 }
-                // 4 => Self::SkyController,
-                // 8 => Self::PowerUp,
+                4 => read_known(src, &mut offset, ctx, Self::SkyController)?,
+                8 => read_known(src, &mut offset, ctx, Self::PowerUp)?,
                 // 133 => Self::Generic,
                 // 134 => Self::FollowMe,
                 // 135 => Self::Wifi,
@@ -135,8 +186,11 @@ pub mod scroll_impl {
                 // 140 => Self::ControllerInfo,
                 // 141 => Self::MapperMini,
                 // 142 => Self::ThermalCam,
+                143 => read_known(src, &mut offset, ctx, Self::Camera)?,
                 // 144 => Self::Animation,
                 // 147 => Self::SequoiaCam,
+                148 => read_known(src, &mut offset, ctx, Self::Gimbal)?,
+                149 => read_known(src, &mut offset, ctx, Self::Battery)?,
                 // value => {
                 //     return Err(Self::Error::OutOfBound {
                 //         value: value.into(),
@@ -164,22 +218,74 @@ pub mod scroll_impl {
             this.gwrite_with::<u8>((&self).into(), &mut offset, ctx)?;
 
             match self {
-                // Self::Common(common) => {
-                //     this.gwrite_with(common, &mut offset, ctx)?;
-                // }
+                Self::Common(common) => {
+                    if let Some(common) = common {
+                        this.gwrite_with(common, &mut offset, ctx)?;
+                    }
+                    // else leave it empty
+                }
                 Self::ArDrone3(ardrone3) => {
                     if let Some(ardrone3) = ardrone3 {
                         this.gwrite_with(ardrone3, &mut offset, ctx)?;
                     }
                     // else leave it empty
                 }
+                Self::Minidrone(minidrone) => {
+                    if let Some(minidrone) = minidrone {
+                        this.gwrite_with(minidrone, &mut offset, ctx)?;
+                    }
+                    // else leave it empty
+                }
+                Self::SkyController(skycontroller) => {
+                    if let Some(skycontroller) = skycontroller {
+                        this.gwrite_with(skycontroller, &mut offset, ctx)?;
+                    }
+                    // else leave it empty
+                }
+                Self::PowerUp(powerup) => {
+                    if let Some(powerup) = powerup {
+                        this.gwrite_with(powerup, &mut offset, ctx)?;
+                    }
+                    // else leave it empty
+                }
+                Self::Camera(camera) => {
+                    if let Some(camera) = camera {
+                        this.gwrite_with(camera, &mut offset, ctx)?;
+                    }
+                    // else leave it empty
+                }
+                Self::Gimbal(gimbal) => {
+                    if let Some(gimbal) = gimbal {
+                        this.gwrite_with(gimbal, &mut offset, ctx)?;
+                    }
+                    // else leave it empty
+                }
+                Self::Battery(battery) => {
+                    if let Some(battery) = battery {
+                        this.gwrite_with(battery, &mut offset, ctx)?;
+                    }
+                    // else leave it empty
+                }
                 Self::JumpingSumo(js) => {
                     this.gwrite_with(js, &mut offset, ctx)?;
                 }
                 Self::Unknown { data, .. } => {
                     this.gwrite_with(data.as_slice(), &mut offset, ())?;
                 }
-                _ => unimplemented!("Not all Features are impled"),
+                Self::Generic
+                | Self::FollowMe
+                | Self::Wifi
+                | Self::RC
+                | Self::DroneManager
+                | Self::Mapper
+                | Self::Debug
+                | Self::ControllerInfo
+                | Self::MapperMini
+                | Self::ThermalCam
+                | Self::Animation
+                | Self::SequoiaCam => {
+                    // no classes are impled, leave it empty
+                }
             }
 
             Ok(offset)
@@ -203,15 +309,30 @@ mod command_tests {
             Feature::ArDrone3(Some(ArDrone3::Piloting(crate::ardrone3::Piloting::TakeOff))),
             1,
         );
-        assert_feature(Feature::Minidrone, 2);
+        assert_feature(
+            Feature::Minidrone(Some(minidrone::Class::Piloting(
+                minidrone::piloting::Command::TakeOff,
+            ))),
+            2,
+        );
         assert_feature(
             Feature::JumpingSumo(jumping_sumo::Class::Piloting(
                 jumping_sumo::PilotingID::Pilot(jumping_sumo::PilotState::default()),
             )),
             3,
         );
-        assert_feature(Feature::SkyController, 4);
-        assert_feature(Feature::PowerUp, 8);
+        assert_feature(
+            Feature::SkyController(Some(skycontroller::Class::Device(
+                skycontroller::device::Command::RequestCurrentDevice,
+            ))),
+            4,
+        );
+        assert_feature(
+            Feature::PowerUp(Some(powerup::Class::Sounds(
+                powerup::sounds::Command::Buzz(powerup::sounds::Buzz { enable: 1 }),
+            ))),
+            8,
+        );
         assert_feature(Feature::Generic, 133);
         assert_feature(Feature::FollowMe, 134);
         assert_feature(Feature::Wifi, 135);
@@ -222,8 +343,38 @@ mod command_tests {
         assert_feature(Feature::ControllerInfo, 140);
         assert_feature(Feature::MapperMini, 141);
         assert_feature(Feature::ThermalCam, 142);
+        assert_feature(Feature::Camera(None), 143);
         assert_feature(Feature::Animation, 144);
         assert_feature(Feature::SequoiaCam, 147);
+        assert_feature(Feature::Gimbal(None), 148);
+        assert_feature(Feature::Battery(None), 149);
+    }
+
+    #[test]
+    fn test_unknown_class_or_command_of_a_known_feature() {
+        use scroll::{Pread, LE};
+
+        // Camera Msgs with a command which is not in `xml/camera.xml`
+        let camera: Feature = [143, 0, 27, 0, 0, 1]
+            .pread_with(0, LE)
+            .expect("Should deserialize");
+        assert_eq!(
+            Feature::Unknown {
+                feature: 143,
+                data: vec![0, 27, 0, 0, 1],
+            },
+            camera
+        );
+
+        // Minidrone with an unknown class
+        let minidrone: Feature = [2, 99, 0, 0].pread_with(0, LE).expect("Should deserialize");
+        assert_eq!(
+            Feature::Unknown {
+                feature: 2,
+                data: vec![99, 0, 0],
+            },
+            minidrone
+        );
     }
 
     fn assert_feature(ref f: Feature, v: u8) {
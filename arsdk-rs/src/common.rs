@@ -10,31 +10,7 @@ pub enum Class {
     SettingsState,  // ARCOMMANDS_ID_COMMON_CLASS_SETTINGSSTATE = 3,
     Common(Common), // ARCOMMANDS_ID_COMMON_CLASS_COMMON = 4,
     /// ARCOMMANDS_ID_COMMON_CLASS_COMMONSTATE = 5,
-    ///
-    /// Bytes: 2 127 0 [12 0 0 0] [0] [5] [1 0] 100
-    /// Common Common BatterStateChanged 100%
-    /// _percent: u8
-    ///
-    /// eARCOMMANDS_ID_COMMON_COMMONSTATE_CMD: u16
-    ////
-    /// typedef enum {
-    ///     ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_ALLSTATESCHANGED = 0,
-    ///     ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_BATTERYSTATECHANGED = 1,
-    ///     ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_MASSSTORAGESTATELISTCHANGED = 2,
-    ///     ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_MASSSTORAGEINFOSTATELISTCHANGED = 3,
-    ///     ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_CURRENTDATECHANGED = 4,
-    ///     ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_CURRENTTIMECHANGED = 5,
-    ///     ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_MASSSTORAGEINFOREMAININGLISTCHANGED = 6,
-    ///     ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_WIFISIGNALCHANGED = 7,
-    ///     ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_SENSORSSTATESLISTCHANGED = 8,
-    ///     ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_PRODUCTMODEL = 9,
-    ///     ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_COUNTRYLISTKNOWN = 10,
-    ///     ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_DEPRECATEDMASSSTORAGECONTENTCHANGED = 11,
-    ///     ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_MASSSTORAGECONTENT = 12,
-    ///     ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_MASSSTORAGECONTENTFORCURRENTRUN = 13,
-    ///     ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_VIDEORECORDINGTIMESTAMP = 14,
-    /// } eARCOMMANDS_ID_COMMON_COMMONSTATE_CMD;
-    CommonState,
+    CommonState(CommonState),
     Overheat,                // ARCOMMANDS_ID_COMMON_CLASS_OVERHEAT = 6,
     OverheatState,           // ARCOMMANDS_ID_COMMON_CLASS_OVERHEATSTATE = 7,
     Controller,              // ARCOMMANDS_ID_COMMON_CLASS_CONTROLLER = 8,
@@ -97,6 +73,41 @@ pub enum Common {
     Reboot,
 }
 
+/// eARCOMMANDS_ID_COMMON_COMMONSTATE_CMD
+/// u16
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CommonState {
+    /// ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_ALLSTATESCHANGED = 0,
+    AllStatesChanged,
+    /// ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_BATTERYSTATECHANGED = 1,
+    ///
+    /// Bytes: 2 127 0 [12 0 0 0] [0] [5] [1 0] 100
+    /// Common CommonState BatteryStateChanged 100%
+    /// _percent: u8
+    BatteryStateChanged(u8),
+    /// ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_WIFISIGNALCHANGED = 7,
+    ///
+    /// _rssi: i16 - RSSI of the signal between controller and the Drone (in dbm)
+    WifiSignalChanged(i16),
+    /// Not yet implemented commands:
+    ///
+    /// ```c
+    /// ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_MASSSTORAGESTATELISTCHANGED = 2,
+    /// ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_MASSSTORAGEINFOSTATELISTCHANGED = 3,
+    /// ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_CURRENTDATECHANGED = 4,
+    /// ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_CURRENTTIMECHANGED = 5,
+    /// ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_MASSSTORAGEINFOREMAININGLISTCHANGED = 6,
+    /// ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_SENSORSSTATESLISTCHANGED = 8,
+    /// ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_PRODUCTMODEL = 9,
+    /// ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_COUNTRYLISTKNOWN = 10,
+    /// ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_DEPRECATEDMASSSTORAGECONTENTCHANGED = 11,
+    /// ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_MASSSTORAGECONTENT = 12,
+    /// ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_MASSSTORAGECONTENTFORCURRENTRUN = 13,
+    /// ARCOMMANDS_ID_COMMON_COMMONSTATE_CMD_VIDEORECORDINGTIMESTAMP = 14,
+    /// ```
+    Unknown { common_state: u16, data: Vec<u8> },
+}
+
 // "yyyy-MM-dd"forCommon.Common.CurrentDate.  Ex:2015-08-27
 fn format_date(date: &DateTime<Utc>) -> CString {
     let format = date.format("%Y-%m-%d").to_string();
@@ -121,7 +132,7 @@ impl Into<u8> for &Class {
             Settings => 2,
             SettingsState => 3,
             Common(_) => 4,
-            CommonState => 5,
+            CommonState(_) => 5,
             Overheat => 6,
             OverheatState => 7,
             Controller => 8,
@@ -155,6 +166,19 @@ impl Into<u8> for &Class {
     }
 }
 
+impl Into<u16> for &CommonState {
+    fn into(self) -> u16 {
+        use CommonState::*;
+
+        match self {
+            AllStatesChanged => 0,
+            BatteryStateChanged(_) => 1,
+            WifiSignalChanged(_) => 7,
+            Unknown { common_state, .. } => *common_state,
+        }
+    }
+}
+
 impl Into<u8> for Common {
     fn into(self) -> u8 {
         match self {
@@ -188,7 +212,7 @@ pub mod scroll_impl {
 
                     Self::Common(common)
                 }
-                // 5 => Self::CommonState,
+                5 => Self::CommonState(src.gread_with(&mut offset, ctx)?),
                 // 6 => Self::Overheat,
                 // 7 => Self::OverheatState,
                 // 8 => Self::Controller,
@@ -238,13 +262,66 @@ pub mod scroll_impl {
                 Self::Common(common) => {
                     this.gwrite_with(common, &mut offset, ctx)?;
                 }
-                _ => unimplemented!("Not all Class are impled"),
+                Self::CommonState(common_state) => {
+                    this.gwrite_with(common_state, &mut offset, ctx)?;
+                }
+                not_impled => {
+                    return Err(Self::Error::NotImplemented(format!(
+                        "Common Class {:?}",
+                        not_impled
+                    )))
+                }
             };
 
             Ok(offset)
         }
     }
 
+    impl<'a> ctx::TryFromCtx<'a, Endian> for CommonState {
+        type Error = Error;
+
+        fn try_from_ctx(src: &'a [u8], ctx: Endian) -> Result<(Self, usize), Self::Error> {
+            let mut offset = 0;
+
+            let common_state = match src.gread_with::<u16>(&mut offset, ctx)? {
+                0 => Self::AllStatesChanged,
+                1 => Self::BatteryStateChanged(src.gread_with(&mut offset, ctx)?),
+                7 => Self::WifiSignalChanged(src.gread_with(&mut offset, ctx)?),
+                unknown => Self::Unknown {
+                    common_state: unknown,
+                    data: crate::parse::read_unknown(src, &mut offset)?,
+                },
+            };
+
+            Ok((common_state, offset))
+        }
+    }
+
+    impl<'a> ctx::TryIntoCtx<Endian> for CommonState {
+        type Error = Error;
+
+        fn try_into_ctx(self, this: &mut [u8], ctx: Endian) -> Result<usize, Self::Error> {
+            let mut offset = 0;
+
+            this.gwrite_with::<u16>((&self).into(), &mut offset, ctx)?;
+
+            match self {
+                Self::AllStatesChanged => {}
+                Self::BatteryStateChanged(percent) => {
+                    this.gwrite_with(percent, &mut offset, ctx)?;
+                }
+                Self::WifiSignalChanged(rssi) => {
+                    this.gwrite_with(rssi, &mut offset, ctx)?;
+                }
+                Self::Unknown { data, .. } => {
+                    this.gwrite_with(data.as_slice(), &mut offset, ())?;
+                }
+            }
+
+            Ok(offset)
+        }
+    }
+
     impl<'a> ctx::TryFromCtx<'a, Endian> for Common {
         type Error = Error;
 
@@ -292,7 +369,12 @@ pub mod scroll_impl {
                     // null terminated C string
                     this.gwrite_with(time.as_bytes_with_nul(), &mut offset, ())?;
                 }
-                _ => unimplemented!("Not all Common are impled"),
+                not_impled => {
+                    return Err(Self::Error::NotImplemented(format!(
+                        "Common command {:?}",
+                        not_impled
+                    )))
+                }
             }
 
             Ok(offset)
@@ -327,7 +409,7 @@ mod common_tests {
         assert_class(Class::Settings, 2);
         assert_class(Class::SettingsState, 3);
         assert_class(Class::Common(Common::AllStates), 4);
-        assert_class(Class::CommonState, 5);
+        assert_class(Class::CommonState(CommonState::AllStatesChanged), 5);
         assert_class(Class::Overheat, 6);
         assert_class(Class::OverheatState, 7);
         assert_class(Class::Controller, 8);
@@ -0,0 +1,296 @@
+use crate::{handshake::ArStream2, link, link::Backoff, record::Recorder};
+use std::{
+    borrow::Cow,
+    net::{IpAddr, Ipv4Addr},
+    sync::Arc,
+    time::Duration,
+};
+
+pub const INIT_PORT: u16 = 44444;
+pub const LISTEN_PORT: u16 = 43210;
+pub const PARROT_SPHINX_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 202, 0, 1));
+pub const PARROT_SPHINX_CONFIG: Config = Config::with_defaults(PARROT_SPHINX_IP);
+/// The SkyController relays the commands to the Drone paired with it, see `relay::SkyController`
+pub const SKYCONTROLLER_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 168, 53, 1));
+pub const SKYCONTROLLER_CONFIG: Config = Config::with_defaults(SKYCONTROLLER_IP);
+/// Same as the ARNetwork `ackTimeoutMs` used for the Bebop's `CDAck` buffer
+pub const DEFAULT_ACK_TIMEOUT: Duration = Duration::from_millis(150);
+pub const DEFAULT_ACK_RETRIES: u8 = 5;
+pub const DEFAULT_CONTROLLER_NAME: &str = "arsdk-rs";
+pub const DEFAULT_CONTROLLER_TYPE: &str = "computer";
+// Anafi4k:
+// self.stream_port = 55004
+// self.stream_control_port = 55005
+pub const DEFAULT_ARSTREAM2: ArStream2 = ArStream2 {
+    stream_port: 44445,
+    control_port: 44446,
+};
+/// How many times connecting for the Handshake is retried
+pub const DEFAULT_HANDSHAKE_RETRIES: usize = 10;
+pub const DEFAULT_HANDSHAKE_CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+pub const DEFAULT_HANDSHAKE_READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Use the `ConfigBuilder` for changing only some of the defaults:
+///
+/// ```rust
+/// use arsdk_rs::{Config, PARROT_SPHINX_IP};
+/// use std::time::Duration;
+///
+/// let config = Config::builder(PARROT_SPHINX_IP)
+///     .listen_port(43211)
+///     .controller_name("second-controller")
+///     .handshake_retries(3)
+///     .build()
+///     .expect("Should be a valid Config");
+///
+/// assert_eq!(43211, config.listen_port);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub drone_addr: IpAddr,
+    /// Wheather or not to set after connecting (by sending a frame) the current DateTime to the Drone:
+    ///
+    /// ```rust
+    /// use chrono::{DateTime, Utc};
+    /// let now: DateTime<Utc> = Utc::now();
+    /// ```
+    pub send_datetime: bool,
+    /// Local ip to bind the sockets to, instead of looking for it.
+    ///
+    /// Needed behind NAT, in containers or if the interfaces can't be listed without the `pnet` feature.
+    pub local_addr: Option<IpAddr>,
+    /// TCP port of the Drone for the Handshake
+    pub init_port: u16,
+    /// Local UDP port at which we listen for the frames of the Drone (`d2c_port`),
+    /// `0` for any free port
+    pub listen_port: u16,
+    /// Sent to the Drone in the Handshake
+    pub controller_name: Cow<'static, str>,
+    /// Sent to the Drone in the Handshake
+    pub controller_type: Cow<'static, str>,
+    /// Ports for the video stream, sent to the Drone in the Handshake if set
+    pub arstream2: Option<ArStream2>,
+    /// How many times connecting for the Handshake is retried
+    pub handshake_retries: usize,
+    pub handshake_connect_timeout: Duration,
+    /// How long to wait for the Handshake response
+    pub handshake_read_timeout: Duration,
+    /// How long to wait for the Ack of a `Type::DataWithAck` frame before re-sending it
+    pub ack_timeout: Duration,
+    /// How many times a `Type::DataWithAck` frame is re-sent before giving up with `Error::AckTimeout`
+    pub ack_retries: u8,
+    /// The link becomes `LinkState::Degraded` if no frame was received from the Drone for this long
+    pub degraded_after: Duration,
+    /// The link becomes `LinkState::Lost` if no frame was received from the Drone for this long
+    pub lost_after: Duration,
+    /// Whether to redo the Handshake and re-open the sockets once the link is `LinkState::Lost`
+    pub reconnect: Option<Backoff>,
+    /// Records every sent and received datagram, e.g. for replaying a field session
+    pub recorder: Option<Arc<Recorder>>,
+}
+
+impl Config {
+    pub fn builder(drone_addr: impl Into<IpAddr>) -> ConfigBuilder {
+        ConfigBuilder {
+            config: Self::with_defaults(drone_addr.into()),
+        }
+    }
+
+    const fn with_defaults(drone_addr: IpAddr) -> Self {
+        Self {
+            drone_addr,
+            // @TODO: Once we fix the Date Time sending, set to `TRUE`
+            send_datetime: false,
+            local_addr: None,
+            init_port: INIT_PORT,
+            listen_port: LISTEN_PORT,
+            controller_name: Cow::Borrowed(DEFAULT_CONTROLLER_NAME),
+            controller_type: Cow::Borrowed(DEFAULT_CONTROLLER_TYPE),
+            // TODO: Check when we don't need `arstream2`
+            // the pyparrot has a check for setting `arstream2` when:
+            // `if(self.drone_type in ("Anafi", "Bebop", "Bebop2", "Disco")):`
+            arstream2: Some(DEFAULT_ARSTREAM2),
+            handshake_retries: DEFAULT_HANDSHAKE_RETRIES,
+            handshake_connect_timeout: DEFAULT_HANDSHAKE_CONNECT_TIMEOUT,
+            handshake_read_timeout: DEFAULT_HANDSHAKE_READ_TIMEOUT,
+            ack_timeout: DEFAULT_ACK_TIMEOUT,
+            ack_retries: DEFAULT_ACK_RETRIES,
+            degraded_after: link::DEFAULT_DEGRADED_AFTER,
+            lost_after: link::DEFAULT_LOST_AFTER,
+            reconnect: None,
+            recorder: None,
+        }
+    }
+}
+
+impl<I> From<I> for Config
+where
+    I: Into<IpAddr>,
+{
+    fn from(ip: I) -> Self {
+        Self::with_defaults(ip.into())
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum ConfigError {
+    #[error("The ack timeout can't be zero")]
+    ZeroAckTimeout,
+    #[error("The link should become Degraded after {degraded_after:?}, before it's Lost after {lost_after:?}")]
+    DegradedAfterLost {
+        degraded_after: Duration,
+        lost_after: Duration,
+    },
+}
+
+/// Builds a `Config` starting from the defaults, see `Config::builder()`
+#[derive(Debug, Clone)]
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl ConfigBuilder {
+    pub fn send_datetime(mut self, send_datetime: bool) -> Self {
+        self.config.send_datetime = send_datetime;
+        self
+    }
+
+    pub fn local_addr(mut self, local_addr: impl Into<IpAddr>) -> Self {
+        self.config.local_addr = Some(local_addr.into());
+        self
+    }
+
+    pub fn init_port(mut self, init_port: u16) -> Self {
+        self.config.init_port = init_port;
+        self
+    }
+
+    pub fn listen_port(mut self, listen_port: u16) -> Self {
+        self.config.listen_port = listen_port;
+        self
+    }
+
+    pub fn controller_name(mut self, controller_name: impl Into<Cow<'static, str>>) -> Self {
+        self.config.controller_name = controller_name.into();
+        self
+    }
+
+    pub fn controller_type(mut self, controller_type: impl Into<Cow<'static, str>>) -> Self {
+        self.config.controller_type = controller_type.into();
+        self
+    }
+
+    /// `None` for not sending the video stream ports in the Handshake
+    pub fn arstream2(mut self, arstream2: Option<ArStream2>) -> Self {
+        self.config.arstream2 = arstream2;
+        self
+    }
+
+    pub fn handshake_retries(mut self, handshake_retries: usize) -> Self {
+        self.config.handshake_retries = handshake_retries;
+        self
+    }
+
+    pub fn handshake_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config.handshake_connect_timeout = timeout;
+        self
+    }
+
+    pub fn handshake_read_timeout(mut self, timeout: Duration) -> Self {
+        self.config.handshake_read_timeout = timeout;
+        self
+    }
+
+    pub fn ack_timeout(mut self, ack_timeout: Duration) -> Self {
+        self.config.ack_timeout = ack_timeout;
+        self
+    }
+
+    pub fn ack_retries(mut self, ack_retries: u8) -> Self {
+        self.config.ack_retries = ack_retries;
+        self
+    }
+
+    pub fn degraded_after(mut self, degraded_after: Duration) -> Self {
+        self.config.degraded_after = degraded_after;
+        self
+    }
+
+    pub fn lost_after(mut self, lost_after: Duration) -> Self {
+        self.config.lost_after = lost_after;
+        self
+    }
+
+    /// `None` for not reconnecting
+    pub fn reconnect(mut self, reconnect: Option<Backoff>) -> Self {
+        self.config.reconnect = reconnect;
+        self
+    }
+
+    pub fn recorder(mut self, recorder: Recorder) -> Self {
+        self.config.recorder = Some(Arc::new(recorder));
+        self
+    }
+
+    /// Fails for a zero `ack_timeout` or if the link wouldn't become `LinkState::Degraded`
+    /// before it's `LinkState::Lost`
+    pub fn build(self) -> Result<Config, ConfigError> {
+        let config = self.config;
+
+        if config.ack_timeout == Duration::from_secs(0) {
+            return Err(ConfigError::ZeroAckTimeout);
+        }
+
+        if config.degraded_after >= config.lost_after {
+            return Err(ConfigError::DegradedAfterLost {
+                degraded_after: config.degraded_after,
+                lost_after: config.lost_after,
+            });
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_build() {
+        let config = Config::builder(PARROT_SPHINX_IP)
+            .ack_timeout(Duration::from_millis(100))
+            .degraded_after(Duration::from_millis(500))
+            .lost_after(Duration::from_secs(2))
+            .build()
+            .expect("Should be a valid Config");
+
+        assert_eq!(Duration::from_millis(100), config.ack_timeout);
+        assert!(Config::builder(PARROT_SPHINX_IP).build().is_ok());
+    }
+
+    #[test]
+    fn test_zero_ack_timeout_is_rejected() {
+        let result = Config::builder(PARROT_SPHINX_IP)
+            .ack_timeout(Duration::from_secs(0))
+            .build();
+
+        assert_eq!(Some(ConfigError::ZeroAckTimeout), result.err());
+    }
+
+    #[test]
+    fn test_degraded_after_lost_is_rejected() {
+        let result = Config::builder(PARROT_SPHINX_IP)
+            .degraded_after(Duration::from_secs(2))
+            .lost_after(Duration::from_secs(2))
+            .build();
+
+        assert_eq!(
+            Some(ConfigError::DegradedAfterLost {
+                degraded_after: Duration::from_secs(2),
+                lost_after: Duration::from_secs(2),
+            }),
+            result.err()
+        );
+    }
+}
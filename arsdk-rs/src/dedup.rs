@@ -0,0 +1,115 @@
+use crate::frame::BufferID;
+use dashmap::DashMap;
+
+/// How many of the latest sequence ids of each `BufferID` are remembered
+const WINDOW: u8 = 32;
+
+/// Sliding window of the sequence ids received on a single `BufferID`
+#[derive(Debug, Clone, Copy)]
+struct Window {
+    /// The newest received sequence id
+    latest: u8,
+    /// Bit `n` is set if `latest - n` was received
+    received: u32,
+}
+
+/// Detects the frames that the Drone re-sends because our Ack got lost.
+///
+/// The sequence ids wrap around after 255, so a sequence id up to 127 ahead of the newest one
+/// is a new frame, otherwise it's an older one which is a duplicate if it's in the window.
+#[derive(Debug, Default)]
+pub(crate) struct Deduplicator {
+    windows: DashMap<BufferID, Window>,
+}
+
+impl Deduplicator {
+    /// Records the sequence id and returns `true` if it was already received
+    pub(crate) fn is_duplicate(&self, buffer_id: BufferID, sequence_id: u8) -> bool {
+        let mut window = match self.windows.get_mut(&buffer_id) {
+            Some(window) => window,
+            None => {
+                self.windows.insert(buffer_id, Window::new(sequence_id));
+                return false;
+            }
+        };
+
+        let ahead = sequence_id.wrapping_sub(window.latest);
+        let behind = window.latest.wrapping_sub(sequence_id);
+
+        if ahead == 0 {
+            true
+        } else if ahead < 128 {
+            window.received = window.received.checked_shl(ahead.into()).unwrap_or(0) | 1;
+            window.latest = sequence_id;
+
+            false
+        } else if behind < WINDOW {
+            let bit = 1 << behind;
+            let duplicate = window.received & bit != 0;
+            window.received |= bit;
+
+            duplicate
+        } else {
+            // too old to know, the Drone has probably restarted the sequence
+            *window = Window::new(sequence_id);
+
+            false
+        }
+    }
+
+    /// Forgets the received sequence ids, e.g. the Drone restarts them when we reconnect
+    pub(crate) fn clear(&self) {
+        self.windows.clear();
+    }
+}
+
+impl Window {
+    fn new(sequence_id: u8) -> Self {
+        Self {
+            latest: sequence_id,
+            received: 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_duplicates() {
+        let dedup = Deduplicator::default();
+        let navdata = |sequence_id| dedup.is_duplicate(BufferID::DCNavdata, sequence_id);
+
+        assert!(!navdata(10));
+        assert!(navdata(10));
+        assert!(!navdata(12));
+        // out of order, but not received yet
+        assert!(!navdata(11));
+        assert!(navdata(11));
+        assert!(navdata(10));
+
+        // other buffers have their own window
+        assert!(!dedup.is_duplicate(BufferID::DCEvent, 10));
+
+        dedup.clear();
+        assert!(!navdata(10));
+    }
+
+    #[test]
+    fn test_wraparound() {
+        let dedup = Deduplicator::default();
+        let navdata = |sequence_id| dedup.is_duplicate(BufferID::DCNavdata, sequence_id);
+
+        assert!(!navdata(254));
+        assert!(!navdata(255));
+        assert!(!navdata(0));
+        assert!(!navdata(1));
+        assert!(navdata(255));
+        assert!(navdata(0));
+
+        // far behind the window
+        assert!(!navdata(150));
+        assert!(navdata(150));
+    }
+}
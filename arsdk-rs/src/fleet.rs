@@ -0,0 +1,232 @@
+//! Connecting to multiple Drones from one process.
+//!
+//! Each `Drone` in the `Fleet` gets its own listen port (`d2c_port`)
+//! and keeps its own sequence ids.
+//!
+//! ```ignore
+//! use arsdk_rs::{fleet::Fleet, prelude::*};
+//!
+//! let mut fleet = Fleet::new();
+//! fleet.connect(Config::from([10, 202, 0, 1]))?;
+//! fleet.connect(Config::from([10, 202, 1, 1]))?;
+//!
+//! for (drone_addr, result) in fleet.emergency() {
+//!     if let Err(err) = result {
+//!         eprintln!("Emergency for {}: {}", drone_addr, err);
+//!     }
+//! }
+//! ```
+use crate::{
+    ardrone3::{ArDrone3, Piloting},
+    command::Feature,
+    frame::{BufferID, Frame, Type},
+    AckHandle, Config, ConnectionError, Drone, Error, LISTEN_PORT,
+};
+use std::{collections::BTreeMap, net::IpAddr, ops::Range};
+
+/// How many Drones can be connected with `Fleet::new()`
+pub const DEFAULT_FLEET_SIZE: u16 = 100;
+
+#[derive(Debug, thiserror::Error)]
+pub enum FleetError {
+    #[error("Drone {0} is already in the Fleet")]
+    AlreadyConnected(IpAddr),
+    #[error("No free listen port left in {0:?}")]
+    NoFreePort(Range<u16>),
+    #[error("Connecting {0}")]
+    Connection(#[from] ConnectionError),
+}
+
+#[derive(Debug)]
+struct Member {
+    drone: Drone,
+    listen_port: u16,
+}
+
+/// Manages the connections to multiple Drones, identified by their address.
+#[derive(Debug)]
+pub struct Fleet {
+    listen_ports: Range<u16>,
+    members: BTreeMap<IpAddr, Member>,
+}
+
+impl Default for Fleet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fleet {
+    /// Allocates the listen ports starting from `LISTEN_PORT`
+    pub fn new() -> Self {
+        Self::with_listen_ports(LISTEN_PORT..LISTEN_PORT + DEFAULT_FLEET_SIZE)
+    }
+
+    /// Allocates the listen ports of the Drones from the given range
+    pub fn with_listen_ports(listen_ports: Range<u16>) -> Self {
+        Self {
+            listen_ports,
+            members: BTreeMap::new(),
+        }
+    }
+
+    /// Connects to the Drone using the first free listen port,
+    /// the `Config::listen_port` is ignored.
+    pub fn connect(&mut self, mut config: Config) -> Result<&Drone, FleetError> {
+        if self.members.contains_key(&config.drone_addr) {
+            return Err(FleetError::AlreadyConnected(config.drone_addr));
+        }
+
+        config.listen_port = self
+            .free_listen_port()
+            .ok_or_else(|| FleetError::NoFreePort(self.listen_ports.clone()))?;
+
+        let drone_addr = config.drone_addr;
+        let member = Member {
+            listen_port: config.listen_port,
+            drone: Drone::connect(config)?,
+        };
+
+        Ok(&self.members.entry(drone_addr).or_insert(member).drone)
+    }
+
+    /// Disconnects the Drone and removes it from the Fleet, freeing its listen port.
+    ///
+    /// Returns `false` if the Drone is not in the Fleet.
+    pub fn disconnect(&mut self, drone_addr: IpAddr) -> bool {
+        match self.members.remove(&drone_addr) {
+            Some(member) => {
+                member.drone.disconnect();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Disconnects all the Drones
+    pub fn disconnect_all(&mut self) {
+        for (_, member) in std::mem::take(&mut self.members) {
+            member.drone.disconnect();
+        }
+    }
+
+    pub fn get(&self, drone_addr: IpAddr) -> Option<&Drone> {
+        self.members.get(&drone_addr).map(|member| &member.drone)
+    }
+
+    /// The listen port allocated to the Drone
+    pub fn listen_port(&self, drone_addr: IpAddr) -> Option<u16> {
+        self.members
+            .get(&drone_addr)
+            .map(|member| member.listen_port)
+    }
+
+    pub fn drones(&self) -> impl Iterator<Item = (IpAddr, &Drone)> {
+        self.members
+            .iter()
+            .map(|(drone_addr, member)| (*drone_addr, &member.drone))
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Sends a frame to every Drone in the Fleet.
+    ///
+    /// The frame is created for each Drone, so it gets that Drone's sequence id,
+    /// e.g. with `Frame::for_drone`.
+    pub fn broadcast<F>(&self, frame: F) -> Vec<(IpAddr, Result<AckHandle, Error>)>
+    where
+        F: Fn(&Drone) -> Frame,
+    {
+        self.drones()
+            .map(|(drone_addr, drone)| (drone_addr, drone.send_frame(frame(drone))))
+            .collect()
+    }
+
+    /// Cuts the motors of every `ArDrone3` Drone in the Fleet
+    ///
+    /// It's sent on the `BufferID::CDEmergency` buffer, which the Drone handles before the others.
+    pub fn emergency(&self) -> Vec<(IpAddr, Result<AckHandle, Error>)> {
+        self.broadcast(|drone| {
+            let feature = Feature::ArDrone3(Some(ArDrone3::Piloting(Piloting::Emergency)));
+
+            Frame::for_drone(
+                drone,
+                Type::DataWithAck,
+                BufferID::CDEmergency,
+                Some(feature),
+            )
+        })
+    }
+
+    fn free_listen_port(&self) -> Option<u16> {
+        self.listen_ports.clone().find(|port| {
+            self.members
+                .values()
+                .all(|member| member.listen_port != *port)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{CommandSender, PARROT_SPHINX_CONFIG};
+    use std::sync::mpsc::{sync_channel, Receiver};
+
+    fn add_member(fleet: &mut Fleet, drone_addr: [u8; 4]) -> Receiver<Vec<u8>> {
+        let (tx_cmd, rx_cmd) = sync_channel(10);
+        let listen_port = fleet.free_listen_port().expect("Should have a free port");
+        let member = Member {
+            drone: Drone::new(CommandSender::Blocking(tx_cmd), &PARROT_SPHINX_CONFIG),
+            listen_port,
+        };
+        fleet.members.insert(drone_addr.into(), member);
+
+        rx_cmd
+    }
+
+    #[test]
+    fn test_listen_ports() {
+        let mut fleet = Fleet::with_listen_ports(50000..50002);
+
+        add_member(&mut fleet, [10, 202, 0, 1]);
+        add_member(&mut fleet, [10, 202, 1, 1]);
+        assert_eq!(Some(50001), fleet.listen_port([10, 202, 1, 1].into()));
+        assert_eq!(None, fleet.free_listen_port());
+
+        assert!(fleet.disconnect([10, 202, 0, 1].into()));
+        assert!(!fleet.disconnect([10, 202, 0, 1].into()));
+        assert_eq!(Some(50000), fleet.free_listen_port());
+        assert_eq!(1, fleet.len());
+    }
+
+    #[test]
+    fn test_broadcast() {
+        let mut fleet = Fleet::new();
+
+        let first = add_member(&mut fleet, [10, 202, 0, 1]);
+        let second = add_member(&mut fleet, [10, 202, 1, 1]);
+        // the first Drone has already sent a frame
+        fleet
+            .get([10, 202, 0, 1].into())
+            .expect("Should be in the Fleet")
+            .inner
+            .sequence_id(BufferID::CDEmergency);
+
+        let results = fleet.emergency();
+        assert_eq!(2, results.len());
+        assert!(results.iter().all(|(_, result)| result.is_ok()));
+
+        let sent = |rx: Receiver<Vec<u8>>| rx.try_recv().expect("Should be sent");
+        let (first, second) = (sent(first), sent(second));
+        assert_eq!(12, first[1]);
+        assert_eq!(1, first[2]);
+        assert_eq!(0, second[2]);
+    }
+}
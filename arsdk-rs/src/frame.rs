@@ -16,9 +16,11 @@ pub enum Error {
     },
     #[error("Expected {expected} bytes, got {actual}")]
     BytesLength { expected: u32, actual: u32 },
+    #[error("{0} can't be written, it's not implemented yet")]
+    NotImplemented(String),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum FrameType {
     Known(Frame),
     Unknown(UnknownFrame),
@@ -32,7 +34,7 @@ pub struct UnknownFrame {
     data: Vec<u8>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Frame {
     pub frame_type: Type,
     pub buffer_id: BufferID,
@@ -40,7 +42,7 @@ pub struct Frame {
     /// Example of empty feature:
     /// ```bash
     /// [2020-07-25T18:51:13Z DEBUG arsdk_rs] Bytes: 1 139 0 8 0 0 0 1
-    /// [2020-07-25T18:51:13Z INFO  arsdk_rs::parse] Frame: Frame { frame_type: Ack, buffer_id: ACKFromSendWithAck, sequence_id: 0, feature: Some(ArDrone3(None)) }
+    /// [2020-07-25T18:51:13Z INFO  arsdk_rs::parse] Frame: Frame { frame_type: Ack, buffer_id: ACKFromSendWithAck, sequence_id: 0, feature: Some(Unknown { feature: 1, data: [] }) }
     /// ```
     pub feature: Option<command::Feature>,
 }
@@ -166,6 +168,29 @@ pub enum BufferID {
     /// Feature = ArDrone3 = 1
     /// 1 139 1 8 0 0 0 1
     ACKFromSendWithAck = 139,
+    /// 128 + buffer id for `CDEmergency`
+    ACKFromEmergency = 140,
+    /// 128 + buffer id for `DCEvent`, we acknowledge the events of the Drone on it
+    ACKFromEvent = 254,
+    /// 128 + buffer id for `DCNavdata`
+    ACKFromNavdata = 255,
+}
+
+impl BufferID {
+    /// The Acks of the frames sent on a buffer are sent back on 128 + the buffer id
+    pub fn is_ack(self) -> bool {
+        let id: u8 = self.into();
+
+        id >= 128
+    }
+
+    /// The buffer on which the frames sent on this buffer are acknowledged, 128 + the buffer id
+    pub fn ack_buffer(self) -> Option<BufferID> {
+        let id: u8 = self.into();
+
+        id.checked_add(128)
+            .and_then(|ack_id| BufferID::try_from(ack_id).ok())
+    }
 }
 
 // --------------------- Conversion impls --------------------- //
@@ -214,6 +239,9 @@ impl TryFrom<u8> for BufferID {
             126 => Ok(Self::DCEvent),
             127 => Ok(Self::DCNavdata),
             139 => Ok(Self::ACKFromSendWithAck),
+            140 => Ok(Self::ACKFromEmergency),
+            254 => Ok(Self::ACKFromEvent),
+            255 => Ok(Self::ACKFromNavdata),
             _ => Err(Error::OutOfBound {
                 value: v.into(),
                 param: "BufferID".to_string(),
@@ -235,6 +263,9 @@ impl Into<u8> for BufferID {
             Self::DCEvent => 126,
             Self::DCNavdata => 127,
             Self::ACKFromSendWithAck => 139,
+            Self::ACKFromEmergency => 140,
+            Self::ACKFromEvent => 254,
+            Self::ACKFromNavdata => 255,
         }
     }
 }
@@ -254,6 +285,9 @@ impl fmt::Display for BufferID {
             Self::DCEvent => write!(f, "DCEvent"),
             Self::DCNavdata => write!(f, "DCNavdata"),
             Self::ACKFromSendWithAck => write!(f, "ACKFromSendWithAck"),
+            Self::ACKFromEmergency => write!(f, "ACKFromEmergency"),
+            Self::ACKFromEvent => write!(f, "ACKFromEvent"),
+            Self::ACKFromNavdata => write!(f, "ACKFromNavdata"),
         }
     }
 }
@@ -306,7 +340,8 @@ pub mod impl_scroll {
 
                 // for the PING & PONG we don't reasonable data
                 // only data to echo back from PING
-                if [BufferID::PING, BufferID::PONG].contains(&buffer_id) {
+                // and the Acks (128 + acknowledged buffer id) carry only the acknowledged sequence id
+                if [BufferID::PING, BufferID::PONG].contains(&buffer_id) || buffer_id.is_ack() {
                     // even if it's a known one, the PING doesn't send sane data
                     let feature = src[..buf_len_usize].gread_with(&mut actual_buf_len, ctx)?;
 
@@ -432,6 +467,9 @@ pub mod impl_scroll {
                 126 => Self::DCEvent,
                 127 => Self::DCNavdata,
                 139 => Self::ACKFromSendWithAck,
+                140 => Self::ACKFromEmergency,
+                254 => Self::ACKFromEvent,
+                255 => Self::ACKFromNavdata,
                 value => {
                     return Err(Error::OutOfBound {
                         value: value.into(),
@@ -462,6 +500,7 @@ mod frame_tests {
         ardrone3::{self, ArDrone3},
         common::{self, Class as CommonClass},
         jumping_sumo::*,
+        minidrone,
     };
     use chrono::{TimeZone, Utc};
     use scroll::{Pread, Pwrite, LE};
@@ -591,6 +630,172 @@ mod frame_tests {
         assert_frames_match(&message, frame);
     }
 
+    #[test]
+    fn test_minidrone_flip_command() {
+        use crate::minidrone::animations::{Command, Flip, FlipDirection};
+
+        //                        type buf  seq  [      len         ] [MD  Anim Flip       back                ]
+        let message: [u8; 15] = [
+            0x4, 0xb, 0x7, 0xf, 0x0, 0x0, 0x0, 0x2, 0x4, 0x0, 0x0, 0x1, 0x0, 0x0, 0x0,
+        ];
+
+        let frame = Frame {
+            frame_type: Type::DataWithAck,
+            buffer_id: BufferID::CDAck,
+            sequence_id: 7,
+            feature: Some(Feature::Minidrone(Some(minidrone::Class::Animations(
+                Command::Flip(Flip {
+                    direction: FlipDirection::Back,
+                }),
+            )))),
+        };
+
+        assert_frames_match(&message, frame);
+    }
+
+    #[test]
+    fn test_minidrone_claw_state_event() {
+        use crate::minidrone::usb_accessory_state::{ClawState, ClawStateState, Command};
+
+        //                        type buf  seq  [      len         ] [MD  USB  Claw       id   CLOSED              flags]
+        let message: [u8; 17] = [
+            0x4, 0x7e, 0x3, 0x11, 0x0, 0x0, 0x0, 0x2, 0xc, 0x1, 0x0, 0x0, 0x2, 0x0, 0x0, 0x0, 0x3,
+        ];
+
+        let frame = Frame {
+            frame_type: Type::DataWithAck,
+            buffer_id: BufferID::DCEvent,
+            sequence_id: 3,
+            feature: Some(Feature::Minidrone(Some(
+                minidrone::Class::UsbAccessoryState(Command::ClawState(ClawState {
+                    id: 0,
+                    state: ClawStateState::Closed,
+                    list_flags: 3,
+                })),
+            ))),
+        };
+
+        assert_frames_match(&message, frame);
+    }
+
+    #[test]
+    fn test_powerup_pcmd_command() {
+        use crate::powerup::{
+            self,
+            piloting::{Command, Pcmd},
+        };
+
+        //                        type buf  seq   [      len         ] [PU  Pil  PCMD       flag thr   roll]
+        let message: [u8; 14] = [
+            0x2, 0xa, 0x21, 0xe, 0x0, 0x0, 0x0, 0x8, 0x0, 0x0, 0x0, 0x1, 0x32, 0xec,
+        ];
+
+        let frame = Frame {
+            frame_type: Type::Data,
+            buffer_id: BufferID::CDNonAck,
+            sequence_id: 0x21,
+            feature: Some(Feature::PowerUp(Some(powerup::Class::Piloting(
+                Command::Pcmd(Pcmd {
+                    flag: 1,
+                    throttle: 50,
+                    roll: -20,
+                }),
+            )))),
+        };
+
+        assert_frames_match(&message, frame);
+    }
+
+    #[test]
+    fn test_camera_zoom_level_event_from_anafi() {
+        use crate::camera::{
+            self,
+            msgs::{Command, ZoomLevel},
+        };
+
+        //                        type buf  seq [    len    ] [Cam Msgs ZoomLevel cam [     1.0     ]]
+        let message: [u8; 16] = [4, 126, 3, 16, 0, 0, 0, 143, 0, 22, 0, 0, 0, 0, 128, 63];
+
+        let frame = Frame {
+            frame_type: Type::DataWithAck,
+            buffer_id: BufferID::DCEvent,
+            sequence_id: 3,
+            feature: Some(Feature::Camera(Some(camera::Class::Msgs(
+                Command::ZoomLevel(ZoomLevel {
+                    cam_id: 0,
+                    level: 1.0,
+                }),
+            )))),
+        };
+
+        assert_frames_match(&message, frame);
+    }
+
+    #[test]
+    fn test_gimbal_attitude_event_from_anafi() {
+        use crate::gimbal::{
+            self,
+            msgs::{Attitude, Command},
+            FrameOfReference,
+        };
+
+        let message: [u8; 48] = [
+            2, 127, 5, 48, 0, 0, 0, // header
+            148, 0, 6, 0, // Gimbal Msgs Attitude
+            0, // gimbal id
+            2, 0, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0, // yaw, pitch & roll frames of reference
+            223, 46, 229, 182, 0, 0, 0, 0, 0, 0, 0, 0, // yaw, pitch & roll relative
+            222, 4, 180, 66, 0, 0, 0, 128, 0, 0, 0, 0, // yaw, pitch & roll absolute
+        ];
+
+        let frame = Frame {
+            frame_type: Type::Data,
+            buffer_id: BufferID::DCNavdata,
+            sequence_id: 5,
+            feature: Some(Feature::Gimbal(Some(gimbal::Class::Msgs(
+                Command::Attitude(Attitude {
+                    gimbal_id: 0,
+                    yaw_frame_of_reference: FrameOfReference::Absolute,
+                    pitch_frame_of_reference: FrameOfReference::Absolute,
+                    roll_frame_of_reference: FrameOfReference::Absolute,
+                    yaw_relative: f32::from_le_bytes([223, 46, 229, 182]),
+                    pitch_relative: 0.0,
+                    roll_relative: 0.0,
+                    yaw_absolute: f32::from_le_bytes([222, 4, 180, 66]),
+                    pitch_absolute: -0.0,
+                    roll_absolute: 0.0,
+                }),
+            )))),
+        };
+
+        assert_frames_match(&message, frame);
+    }
+
+    #[test]
+    fn test_battery_health_event_from_anafi() {
+        use crate::battery::{
+            self,
+            msgs::{Command, Health},
+        };
+
+        //                        type buf  seq [    len    ] [Bat Msgs Health  soh temp]
+        let message: [u8; 13] = [4, 126, 14, 13, 0, 0, 0, 149, 0, 3, 0, 91, 33];
+
+        let frame = Frame {
+            frame_type: Type::DataWithAck,
+            buffer_id: BufferID::DCEvent,
+            sequence_id: 14,
+            feature: Some(Feature::Battery(Some(battery::Class::Msgs(
+                Command::Health(Health {
+                    state_of_health: 91,
+                    temperature: 33,
+                }),
+            )))),
+        };
+
+        assert_frames_match(&message, frame);
+    }
+
     #[test]
     fn test_ping_feature_from_anafi4k() {
         let message: [u8; 15] = [2, 0, 2, 15, 0, 0, 0, 155, 216, 221, 13, 0, 0, 0, 0];
@@ -608,8 +813,6 @@ mod frame_tests {
     }
 
     #[test]
-    #[ignore]
-    // TODO: Impl CommonState!
     fn test_feature_common_state() {
         let message: [u8; 12] = [
             2, 127, 20, 12, 0, 0, 0, // common
@@ -622,19 +825,20 @@ mod frame_tests {
             frame_type: Type::Data,
             buffer_id: BufferID::DCNavdata,
             sequence_id: 20,
-            feature: Some(command::Feature::Common(Some(CommonClass::CommonState))),
+            feature: Some(command::Feature::Common(Some(CommonClass::CommonState(
+                common::CommonState::BatteryStateChanged(100),
+            )))),
         };
 
         assert_frames_match(&message, frame);
     }
 
     #[test]
-    #[ignore = "We've received it from Anafi4k and it doesn't make sense atm"]
     /// [1] Type::Ack
     /// [139] BufferID::ACKFromSendWithAck
     /// [4] Sequence ID
     /// [8, 0, 0, 0] 8 length
-    /// [3] Jumping Sumo
+    /// [3] Acknowledged sequence id
     fn test_unknown_jumping_sumo_feature_from_anafi() {
         let message: [u8; 8] = [1, 139, 4, 8, 0, 0, 0, 3];
 
@@ -678,6 +882,30 @@ mod frame_tests {
         assert_frames_match(&message, frame);
     }
 
+    #[test]
+    /// [4] Type::DataWithAck
+    /// [126] BufferID::DCEvent
+    /// [2] Sequence ID
+    /// [12, 0, 0, 0] 12 length
+    /// [1] ArDrone3
+    /// [31] GPS state
+    /// [0, 0] Number of satellite changed
+    /// [12] Satellites
+    fn test_ardrone3_gps_state_feature() {
+        let message: [u8; 12] = [4, 126, 2, 12, 0, 0, 0, 1, 31, 0, 0, 12];
+
+        let frame = Frame {
+            frame_type: Type::DataWithAck,
+            buffer_id: BufferID::DCEvent,
+            sequence_id: 2,
+            feature: Some(Feature::ArDrone3(Some(ArDrone3::GPSState(
+                ardrone3::GPSState::NumberOfSatelliteChanged(12),
+            )))),
+        };
+
+        assert_frames_match(&message, frame);
+    }
+
     fn assert_frames_match(expected: &[u8], frame: Frame) {
         // Check the value at the Frame length bytes 3 to 7
         let buf_len: u32 = (&expected[3..7])
@@ -727,6 +955,29 @@ mod frame_tests {
         assert_command(BufferID::DCVideo, 125);
         assert_command(BufferID::DCEvent, 126);
         assert_command(BufferID::DCNavdata, 127);
+        assert_command(BufferID::ACKFromSendWithAck, 139);
+        assert_command(BufferID::ACKFromEmergency, 140);
+        assert_command(BufferID::ACKFromEvent, 254);
+        assert_command(BufferID::ACKFromNavdata, 255);
+    }
+
+    #[test]
+    fn test_ack_buffer() {
+        assert_eq!(
+            Some(BufferID::ACKFromSendWithAck),
+            BufferID::CDAck.ack_buffer()
+        );
+        assert_eq!(
+            Some(BufferID::ACKFromEmergency),
+            BufferID::CDEmergency.ack_buffer()
+        );
+        assert_eq!(Some(BufferID::ACKFromEvent), BufferID::DCEvent.ack_buffer());
+        assert_eq!(
+            Some(BufferID::ACKFromNavdata),
+            BufferID::DCNavdata.ack_buffer()
+        );
+        assert_eq!(None, BufferID::CDNonAck.ack_buffer());
+        assert_eq!(None, BufferID::ACKFromEvent.ack_buffer());
     }
 
     fn assert_frame(t: Type, v: u8) {
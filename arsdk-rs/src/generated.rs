@@ -0,0 +1,111 @@
+//! The commands generated from the arsdk-xml definitions in `arsdk-rs/xml`.
+//!
+//! Every feature is a module with a `Class` enum, every class a module with a `Command` enum.
+//! The `Class` of each feature is held by its `command::Feature` variant,
+//! e.g. `command::Feature::Minidrone(Some(minidrone::Class))`.
+//!
+//! ```
+//! use arsdk_rs::{command::Feature, generated::minidrone};
+//! use scroll::{Pread, LE};
+//!
+//! let feature: Feature = [2_u8, 0, 1, 0].pread_with(0, LE).expect("Should read");
+//!
+//! assert_eq!(
+//!     Feature::Minidrone(Some(minidrone::Class::Piloting(
+//!         minidrone::piloting::Command::TakeOff
+//!     ))),
+//!     feature
+//! );
+//! ```
+include!(concat!(env!("OUT_DIR"), "/generated.rs"));
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{command::Feature, frame::Error};
+    use scroll::{Pread, Pwrite, LE};
+
+    fn round_trip(feature: Feature, expected: &[u8]) {
+        let mut buf = [0_u8; 256];
+        let written = buf
+            .pwrite_with(feature.clone(), 0, LE)
+            .expect("Should write");
+        assert_eq!(expected, &buf[..written]);
+
+        let mut offset = 0;
+        let read: Feature = expected.gread_with(&mut offset, LE).expect("Should read");
+        assert_eq!(written, offset);
+        assert_eq!(feature, read);
+    }
+
+    #[test]
+    fn test_minidrone_pcmd() {
+        let pcmd = minidrone::piloting::Pcmd {
+            flag: 1,
+            roll: -10,
+            pitch: 20,
+            yaw: 0,
+            gaz: -100,
+            timestamp: 0x0100_0000,
+        };
+
+        round_trip(
+            Feature::Minidrone(Some(minidrone::Class::Piloting(
+                minidrone::piloting::Command::Pcmd(pcmd),
+            ))),
+            &[2, 0, 2, 0, 1, 246, 20, 0, 156, 0, 0, 0, 1],
+        );
+    }
+
+    #[test]
+    fn test_flying_state_changed() {
+        use minidrone::piloting_state::{Command, FlyingStateChanged, FlyingStateChangedState};
+
+        let class: minidrone::Class = [3_u8, 1, 0, 7, 0, 0, 0]
+            .pread_with(0, LE)
+            .expect("Should read");
+
+        assert_eq!(
+            minidrone::Class::PilotingState(Command::FlyingStateChanged(FlyingStateChanged {
+                state: FlyingStateChangedState::Init
+            })),
+            class
+        );
+
+        match [3_u8, 1, 0, 9, 0, 0, 0].pread_with::<minidrone::Class>(0, LE) {
+            Err(Error::OutOfBound { value: 9, .. }) => {}
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_strings() {
+        use skycontroller::wifi_list::{Command, ForgetWifi};
+
+        round_trip(
+            Feature::SkyController(Some(skycontroller::Class::WifiList(Command::ForgetWifi(
+                ForgetWifi {
+                    ssid: "Bebop2-000000".to_string(),
+                },
+            )))),
+            b"\x04\x00\x03\x00Bebop2-000000\x00",
+        );
+
+        assert!([0_u8, 3, 0, b'B', b'e']
+            .pread_with::<skycontroller::Class>(0, LE)
+            .is_err());
+    }
+
+    #[test]
+    fn test_unknown_ids() {
+        match [0_u8, 200, 0].pread_with::<minidrone::Class>(0, LE) {
+            Err(Error::OutOfBound { value: 200, .. }) => {}
+            result => panic!("Unexpected result: {:?}", result),
+        }
+
+        match [250_u8, 0, 0].pread_with::<minidrone::Class>(0, LE) {
+            Err(Error::OutOfBound { value: 250, .. }) => {}
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::Config;
 use log::{error, info};
 use serde::{Deserialize, Serialize};
 use serde_with::with_prefix;
@@ -47,10 +48,11 @@ pub struct Response {
 with_prefix!(prefix_arstream2_client "arstream2_client_");
 with_prefix!(prefix_arstream2_server "arstream2_server_");
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Local ports for the video stream of the Drone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ArStream2 {
-    stream_port: u16,
-    control_port: u16,
+    pub stream_port: u16,
+    pub control_port: u16,
 }
 #[derive(Debug, Error)]
 pub enum Error {
@@ -67,30 +69,17 @@ pub enum Error {
     ResponseString(#[from] FromUtf8Error),
 }
 
-pub(crate) fn perform_handshake(
-    init_address: SocketAddr,
-    d2c_port: u16,
-) -> Result<Response, Error> {
-    let request = Request {
-        controller_name: "arsdk-rs".to_string(),
-        controller_type: "computer".to_string(),
-        d2c_port,
-        // Anafi4k:
-        // self.stream_port = 55004
-        // self.stream_control_port = 55005
-        arstream2: Some(ArStream2 {
-            stream_port: 44445,
-            control_port: 44446,
-        }),
-        // TODO: Check when we don't need `arstream2`
-        // the pyparrot has a check for setting `arstream2` when:
-        // `if(self.drone_type in ("Anafi", "Bebop", "Bebop2", "Disco")):`
-        // arstream2: None,
-    };
+/// `d2c_port` is the port of the already bound Listener, which can differ from `Config::listen_port`
+/// if it's `0`
+pub(crate) fn perform_handshake(config: &Config, d2c_port: u16) -> Result<Response, Error> {
+    let init_address = SocketAddr::new(config.drone_addr, config.init_port);
+    info!("Init address {}", &init_address);
+
+    let request = request(config, d2c_port);
 
     info!("Connecting controller {}", request.controller_name);
 
-    let mut handshake_stream = retry(10, init_address)?;
+    let mut handshake_stream = retry(config, init_address)?;
 
     info!("Request: {}", serde_json::to_string(&request)?);
     let request_string = serde_json::to_vec(&request)?;
@@ -101,12 +90,61 @@ pub(crate) fn perform_handshake(
     let read = handshake_stream.read(&mut buf)?;
     info!("Read {} bytes!", read);
 
-    let response_string = String::from_utf8(buf[..read].to_vec())?;
+    handshake_stream.shutdown(Shutdown::Both)?;
 
-    info!("Response: {}", response_string);
+    response(&buf[..read])
+}
+
+/// Same as `perform_handshake` but using the `tokio` `TcpStream`
+#[cfg(feature = "tokio")]
+pub(crate) async fn perform_handshake_async(
+    config: &Config,
+    d2c_port: u16,
+) -> Result<Response, Error> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let init_address = SocketAddr::new(config.drone_addr, config.init_port);
+    info!("Init address {}", &init_address);
+
+    let request = request(config, d2c_port);
+
+    info!("Connecting controller {}", request.controller_name);
+
+    let mut handshake_stream = retry_async(config, init_address).await?;
+
+    info!("Request: {}", serde_json::to_string(&request)?);
+    let request_string = serde_json::to_vec(&request)?;
+
+    handshake_stream.write_all(&request_string).await?;
+
+    let mut buf = [0_u8; 256];
+    let read = tokio::time::timeout(
+        config.handshake_read_timeout,
+        handshake_stream.read(&mut buf),
+    )
+    .await
+    .map_err(|_| std::io::Error::from(std::io::ErrorKind::TimedOut))??;
+    info!("Read {} bytes!", read);
 
     handshake_stream.shutdown(Shutdown::Both)?;
 
+    response(&buf[..read])
+}
+
+fn request(config: &Config, d2c_port: u16) -> Request {
+    Request {
+        controller_name: config.controller_name.to_string(),
+        controller_type: config.controller_type.to_string(),
+        d2c_port,
+        arstream2: config.arstream2,
+    }
+}
+
+fn response(buf: &[u8]) -> Result<Response, Error> {
+    let response_string = String::from_utf8(buf.to_vec())?;
+
+    info!("Response: {}", response_string);
+
     let response: Response = serde_json::from_str(&response_string.trim_end_matches('\u{0}'))?;
 
     if response.status != 0 {
@@ -116,16 +154,14 @@ pub(crate) fn perform_handshake(
     }
 }
 
-fn retry(times: usize, target: SocketAddr) -> Result<TcpStream, Error> {
-    let connection_timeout = std::time::Duration::from_secs(2);
-    let read_timeout = std::time::Duration::from_secs(2);
+fn retry(config: &Config, target: SocketAddr) -> Result<TcpStream, Error> {
     let mut retry = 0;
 
-    let mut res = TcpStream::connect_timeout(&target, connection_timeout);
+    let mut res = TcpStream::connect_timeout(&target, config.handshake_connect_timeout);
 
-    while res.is_err() && retry < times {
+    while res.is_err() && retry < config.handshake_retries {
         retry += 1;
-        res = TcpStream::connect_timeout(&target, connection_timeout);
+        res = TcpStream::connect_timeout(&target, config.handshake_connect_timeout);
     }
 
     let tcp_stream = match res {
@@ -139,7 +175,40 @@ fn retry(times: usize, target: SocketAddr) -> Result<TcpStream, Error> {
 
     info!("{}: TCP Stream initialized", target);
 
-    tcp_stream.set_read_timeout(Some(read_timeout))?;
+    tcp_stream.set_read_timeout(Some(config.handshake_read_timeout))?;
+
+    Ok(tcp_stream)
+}
+
+#[cfg(feature = "tokio")]
+async fn retry_async(config: &Config, target: SocketAddr) -> Result<tokio::net::TcpStream, Error> {
+    let connect = || async {
+        tokio::time::timeout(
+            config.handshake_connect_timeout,
+            tokio::net::TcpStream::connect(target),
+        )
+        .await
+        .unwrap_or_else(|_| Err(std::io::Error::from(std::io::ErrorKind::TimedOut)))
+    };
+    let mut retry = 0;
+
+    let mut res = connect().await;
+
+    while res.is_err() && retry < config.handshake_retries {
+        retry += 1;
+        res = connect().await;
+    }
+
+    let tcp_stream = match res {
+        Ok(tcp_stream) => tcp_stream,
+        Err(err) => {
+            error!("TCP Stream failed: {}", &err);
+
+            return Err(err.into());
+        }
+    };
+
+    info!("{}: TCP Stream initialized", target);
 
     Ok(tcp_stream)
 }
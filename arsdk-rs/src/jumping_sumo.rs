@@ -181,7 +181,12 @@ pub mod scroll_impl {
                 Self::Animations(anim) => {
                     this.gwrite_with(anim, &mut offset, ctx)?;
                 }
-                _ => unimplemented!("Not all Class are impled"),
+                not_impled => {
+                    return Err(Self::Error::NotImplemented(format!(
+                        "Jumping Sumo Class {:?}",
+                        not_impled
+                    )))
+                }
             }
 
             Ok(offset)
@@ -226,7 +231,12 @@ pub mod scroll_impl {
                 Self::Pilot(state) => {
                     this.gwrite_with(state, &mut offset, ctx)?;
                 }
-                _ => unimplemented!("Not all PilotingID are impled"),
+                not_impled => {
+                    return Err(Self::Error::NotImplemented(format!(
+                        "Jumping Sumo PilotingID {:?}",
+                        not_impled
+                    )))
+                }
             }
 
             Ok(offset)
@@ -365,6 +375,19 @@ mod jumping_dumo_tests {
         assert_class(Class::VideoSettingsState, 22);
     }
 
+    #[test]
+    fn test_not_impled_class_is_not_written() {
+        use crate::frame::Error;
+        use scroll::{Pwrite, LE};
+
+        let mut buf = [0_u8; 8];
+        let err = buf
+            .pwrite_with(Class::PilotingState, 0, LE)
+            .expect_err("PilotingState can't be written");
+
+        assert!(matches!(err, Error::NotImplemented(_)));
+    }
+
     fn assert_class(dc: impl Borrow<Class>, v: u8) {
         let as_u8: u8 = dc.borrow().into();
         assert_eq!(v, as_u8);
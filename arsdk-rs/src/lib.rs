@@ -1,45 +1,87 @@
 use crate::frame::{Frame, FrameType};
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
-use log::{error, info};
-use pnet::datalink;
+use log::{error, info, warn};
 use scroll::{ctx::TryIntoCtx, Pread, LE};
-use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
 use std::sync::{
-    mpsc::{sync_channel, Receiver, SendError, SyncSender},
-    Arc,
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    mpsc::{sync_channel, Receiver, RecvTimeoutError, SendError, SyncSender},
+    Arc, Mutex, PoisonError, RwLock, Weak,
 };
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 // re-export chrono
 pub use chrono;
 
-pub const INIT_PORT: u16 = 44444;
-pub const LISTEN_PORT: u16 = 43210;
-pub const PARROT_SPHINX_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 202, 0, 1));
-pub const PARROT_SPHINX_CONFIG: Config = Config {
-    drone_addr: PARROT_SPHINX_IP,
-    // @TODO: Once we fix the Date Time sending, set to `TRUE`
-    send_datetime: false,
-};
+/// How often the background threads check if the Drone was disconnected
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
+mod ack;
 pub mod ardrone3;
+#[cfg(feature = "tokio")]
+pub mod async_drone;
 pub mod command;
 pub mod common;
+pub mod config;
+mod dedup;
+pub mod fleet;
 pub mod frame;
+pub mod generated;
 mod handshake;
 pub mod jumping_sumo;
+pub mod link;
 pub mod listener;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
 pub mod parse;
-
+pub mod pcap;
+pub mod record;
+pub mod relay;
+pub mod state;
+pub mod subscription;
+
+pub use ack::AckHandle;
+#[cfg(feature = "tokio")]
+pub use async_drone::AsyncDrone;
+pub use config::{
+    Config, ConfigBuilder, ConfigError, DEFAULT_ACK_RETRIES, DEFAULT_ACK_TIMEOUT, INIT_PORT,
+    LISTEN_PORT, PARROT_SPHINX_CONFIG, PARROT_SPHINX_IP, SKYCONTROLLER_CONFIG, SKYCONTROLLER_IP,
+};
+/// The Anafi battery feature, generated from `xml/battery.xml`
+pub use generated::battery;
+/// The Anafi camera feature, generated from `xml/camera.xml`
+pub use generated::camera;
+/// The Anafi gimbal feature, generated from `xml/gimbal.xml`
+pub use generated::gimbal;
+/// The Minidrone feature (Mambo, Swing), generated from `xml/minidrone.xml`
+pub use generated::minidrone;
+/// The PowerUp feature (PowerUp FPV), generated from `xml/powerup.xml`
+pub use generated::powerup;
+/// The SkyController feature, generated from `xml/skycontroller.xml`
+pub use generated::skycontroller;
 pub(crate) use handshake::perform_handshake;
+pub use handshake::ArStream2;
+use link::{LinkState, LinkStats};
 use listener::Listener;
+use record::Direction;
+use state::DroneState;
+use subscription::{Filter, Policy, Subscription};
 
 pub mod prelude {
     pub use crate::{
-        frame, Config, ConnectionError, Drone, Error, PARROT_SPHINX_CONFIG, PARROT_SPHINX_IP,
+        frame,
+        link::{LinkState, LinkStats},
+        state::DroneState,
+        subscription, AckHandle, Config, ConfigBuilder, ConfigError, ConnectionError, Drone, Error,
+        PARROT_SPHINX_CONFIG, PARROT_SPHINX_IP,
     };
     pub use chrono::{DateTime, Utc};
+
+    #[cfg(feature = "tokio")]
+    pub use crate::AsyncDrone;
 }
 
 #[derive(Debug, Error)]
@@ -48,6 +90,13 @@ pub enum Error {
     Send(#[from] SendError<Vec<u8>>),
     #[error("Receiving a Frame")]
     Receive(#[from] frame::Error),
+    #[error("No Ack received for {buffer_id} with sequence id {sequence_id}")]
+    AckTimeout {
+        buffer_id: frame::BufferID,
+        sequence_id: u8,
+    },
+    #[error("The Drone was disconnected")]
+    Disconnected,
 }
 
 #[derive(Debug, Error)]
@@ -67,30 +116,6 @@ pub enum ConnectionError {
     Handshake(#[from] handshake::Error),
 }
 
-#[derive(Debug)]
-pub struct Config {
-    pub drone_addr: IpAddr,
-    /// Wheather or not to set after connecting (by sending a frame) the current DateTime to the Drone:
-    ///
-    /// ```rust
-    /// use chrono::{DateTime, Utc};
-    /// let now: DateTime<Utc> = Utc::now();
-    /// ```
-    pub send_datetime: bool,
-}
-
-impl<I> From<I> for Config
-where
-    I: Into<IpAddr>,
-{
-    fn from(ip: I) -> Self {
-        Self {
-            drone_addr: ip.into(),
-            send_datetime: false,
-        }
-    }
-}
-
 #[derive(Clone, Debug)]
 pub struct Drone {
     inner: Arc<DroneInner>,
@@ -100,61 +125,268 @@ pub struct Drone {
 struct DroneInner {
     // Each frame::BufferID gets its own sequence_id
     sequence_ids: DashMap<frame::BufferID, u8>,
-    sender: SyncSender<Vec<u8>>,
+    sender: CommandSender,
+    // `Type::DataWithAck` frames waiting for an Ack from the Drone
+    pending_acks: ack::PendingAcks,
+    subscribers: subscription::Subscribers,
+    recorder: Option<Arc<record::Recorder>>,
+    // Sequence ids of the incoming frames to drop the re-sent ones
+    received_sequence_ids: dedup::Deduplicator,
+    // Latest known state folded from the incoming events
+    state: RwLock<DroneState>,
+    link: link::LinkMonitor,
+    link_stats: link::LinkStatsTracker,
+    // Stops the background threads once set
+    disconnected: AtomicBool,
+    threads: Mutex<Vec<JoinHandle<()>>>,
+    // Bumped on reconnect to stop the Listener and Command sender threads
+    link_generation: AtomicUsize,
+    link_threads: Mutex<Vec<JoinHandle<()>>>,
+}
+
+/// Used by the background threads, so they don't keep the `Drone` connected forever
+#[derive(Debug, Clone)]
+pub(crate) struct WeakDrone {
+    inner: Weak<DroneInner>,
+    link_generation: Option<usize>,
+}
+
+impl WeakDrone {
+    /// Returns `None` if the `Drone` was dropped or disconnected
+    /// or if the link of this `WeakDrone` was closed for reconnecting.
+    pub(crate) fn upgrade(&self) -> Option<Drone> {
+        self.inner
+            .upgrade()
+            .map(|inner| Drone { inner })
+            .filter(|drone| !drone.is_disconnected())
+            .filter(|drone| {
+                self.link_generation.map_or(true, |generation| {
+                    generation == drone.inner.link_generation.load(Ordering::SeqCst)
+                })
+            })
+    }
+}
+
+/// What's needed to (re-)open the link to the Drone
+#[derive(Debug)]
+struct Connection {
+    config: Config,
+    // shared between the Command sender threads of each link
+    commands: Arc<Mutex<Receiver<Vec<u8>>>>,
 }
 
 impl Drone {
     /// Connects to a drone
     ///
-    /// * Spawns Listener at LISTENER_PORT
-    /// * Performs Handshake at INIT_PORT
+    /// * Spawns Listener at `Config::listen_port`
+    /// * Performs Handshake at `Config::init_port`
     /// * Spawns Command sender at `c2d_port`
+    /// * Spawns the re-sender of the not acknowledged `Type::DataWithAck` frames
+    /// * Spawns the Link supervisor, which reconnects to the Drone if `Config::reconnect` is set
+    ///
+    /// The threads are stopped on `Drone::disconnect()` or when the last clone of the `Drone` is dropped.
     pub fn connect(config: Config) -> Result<Self, ConnectionError> {
-        let local_ip = local_ip(config.drone_addr)
-            .ok_or_else(|| ConnectionError::DroneAddr(config.drone_addr))?;
+        let (tx_cmd, rx_cmd) = sync_channel(COMMANDS_CAPACITY);
+
+        let drone = Self::new(CommandSender::Blocking(tx_cmd), &config);
+        let send_datetime = config.send_datetime;
+        let connection = Connection {
+            config,
+            commands: Arc::new(Mutex::new(rx_cmd)),
+        };
 
-        // @TODO: Check if we're going to miss any messages between spawning the listener and the receiver of commands
-        let (tx_cmd, rx_cmd) = sync_channel(200);
+        drone.open_link(&connection)?;
 
-        let drone = Self {
+        spawn_ack_retransmitter(&drone);
+        spawn_link_supervisor(&drone, connection);
+
+        if send_datetime {
+            drone.send_datetime(Utc::now())?;
+        }
+
+        Ok(drone)
+    }
+
+    fn new(sender: CommandSender, config: &Config) -> Self {
+        Self {
             inner: Arc::new(DroneInner {
                 sequence_ids: DashMap::new(),
-                sender: tx_cmd,
+                sender,
+                pending_acks: ack::PendingAcks::new(config.ack_timeout, config.ack_retries),
+                subscribers: Default::default(),
+                recorder: config.recorder.clone(),
+                received_sequence_ids: Default::default(),
+                state: Default::default(),
+                link: link::LinkMonitor::new(config.degraded_after, config.lost_after),
+                link_stats: link::LinkStatsTracker::new(),
+                disconnected: AtomicBool::new(false),
+                threads: Default::default(),
+                link_generation: AtomicUsize::new(0),
+                link_threads: Default::default(),
             }),
-        };
+        }
+    }
 
-        let local_listener = SocketAddr::new(local_ip, LISTEN_PORT);
-        info!("{}: Spawning Listener", &&local_listener);
+    pub(crate) fn downgrade(&self) -> WeakDrone {
+        WeakDrone {
+            inner: Arc::downgrade(&self.inner),
+            link_generation: None,
+        }
+    }
+
+    /// A `WeakDrone` that can't be upgraded once the current link is closed
+    fn downgrade_link(&self) -> WeakDrone {
+        WeakDrone {
+            link_generation: Some(self.inner.link_generation.load(Ordering::SeqCst)),
+            ..self.downgrade()
+        }
+    }
+
+    /// * Spawns Listener at `Config::listen_port`
+    /// * Performs Handshake at `Config::init_port`
+    /// * Spawns Command sender at `c2d_port`
+    fn open_link(&self, connection: &Connection) -> Result<(), ConnectionError> {
+        let config = &connection.config;
+        let local_ip =
+            local_ip(config).ok_or_else(|| ConnectionError::DroneAddr(config.drone_addr))?;
 
-        spawn_listener(drone.clone(), local_listener)?;
+        // the Drone starts the sequence ids of the new link from scratch
+        self.inner.received_sequence_ids.clear();
+        self.inner.link_stats.clear_sequence_ids();
 
-        let init_addr = SocketAddr::new(config.drone_addr, INIT_PORT);
+        let local_listener = SocketAddr::new(local_ip, config.listen_port);
+        info!("{}: Spawning Listener", &&local_listener);
 
-        info!("Init address {}", &init_addr);
+        let d2c_port = spawn_listener(self, local_listener)?;
 
-        let handshake_response = perform_handshake(init_addr, local_listener.port())?;
+        let handshake_response = perform_handshake(config, d2c_port)?;
         let cmd_sender_target = SocketAddr::new(config.drone_addr, handshake_response.c2d_port);
 
         info!("{}: Spawning CMD Sender", cmd_sender_target);
 
-        spawn_cmd_sender(rx_cmd, local_ip, cmd_sender_target)?;
+        spawn_cmd_sender(
+            self,
+            connection.commands.clone(),
+            local_ip,
+            cmd_sender_target,
+        )?;
 
-        if config.send_datetime {
-            drone.send_datetime(Utc::now())?;
+        // give the Drone some time to start sending frames again
+        self.inner.link.received();
+
+        Ok(())
+    }
+
+    /// Stops the Listener and Command sender threads and waits for them to finish
+    fn close_link(&self) {
+        self.inner.link_generation.fetch_add(1, Ordering::SeqCst);
+
+        join_threads(&self.inner.link_threads);
+    }
+
+    /// The current `LinkState` based on the time since the last received frame
+    pub fn link_state(&self) -> LinkState {
+        self.inner.link.state(Instant::now())
+    }
+
+    /// Receives the changes of the `LinkState`, e.g. `LinkState::Lost` when the Drone stops PINGing us.
+    pub fn link_events(&self) -> Receiver<LinkState> {
+        self.inner.link.subscribe()
+    }
+
+    /// Quality metrics of the link, e.g. the missed frames and the round-trip time
+    pub fn link_stats(&self) -> LinkStats {
+        self.inner.link_stats.stats(Instant::now())
+    }
+
+    /// Stops the Listener, Command sender and the re-sender threads and waits for them to finish,
+    /// which closes the UDP sockets and frees the listener port.
+    ///
+    /// The `AckHandle`s of the not yet acknowledged frames are resolved with `Error::Disconnected`
+    /// and any frame sent afterwards fails with `Error::Send`.
+    ///
+    /// It's called when the last clone of the `Drone` is dropped as well.
+    pub fn disconnect(&self) {
+        self.inner.disconnect();
+    }
+
+    pub fn is_disconnected(&self) -> bool {
+        self.inner.disconnected.load(Ordering::SeqCst)
+    }
+
+    /// Sends the frame without waiting for it to be acknowledged.
+    ///
+    /// `Type::DataWithAck` frames are re-sent until the Drone acknowledges them,
+    /// use the returned `AckHandle` to wait for the Ack.
+    pub fn send_frame(&self, frame: frame::Frame) -> Result<AckHandle, Error> {
+        let (handle, raw_message) = self.prepare_frame(frame)?;
+
+        if let Err(err) = self.inner.sender.send(raw_message) {
+            self.forget_frame(&handle);
+
+            return Err(err);
         }
 
-        Ok(drone)
+        Ok(handle)
     }
 
-    pub fn send_frame(&self, frame: frame::Frame) -> Result<(), Error> {
+    /// Nobody waits for the Ack of a frame that couldn't be sent, so it's not re-sent either
+    pub(crate) fn forget_frame(&self, handle: &AckHandle) {
+        self.inner
+            .pending_acks
+            .remove(handle.buffer_id(), handle.sequence_id());
+    }
+
+    /// Serializes the frame and registers the `Type::DataWithAck` frames for re-sending
+    fn prepare_frame(&self, frame: frame::Frame) -> Result<(AckHandle, Vec<u8>), Error> {
+        let (frame_type, buffer_id, sequence_id) =
+            (frame.frame_type, frame.buffer_id, frame.sequence_id);
+
         let mut raw_message = [0_u8; 2048];
         let written = frame.try_into_ctx(&mut raw_message, LE)?;
+        let raw_message = raw_message[0..written].to_vec();
 
-        self.send_raw_message(&raw_message[0..written])
+        let handle = if frame_type == frame::Type::DataWithAck {
+            let (handle, waiter) = AckHandle::pending(buffer_id, sequence_id);
+            self.inner
+                .pending_acks
+                .insert(buffer_id, sequence_id, raw_message.clone(), waiter);
+
+            handle
+        } else {
+            AckHandle::resolved(buffer_id, sequence_id)
+        };
+
+        Ok((handle, raw_message))
+    }
+
+    /// Snapshot of the latest known state of the Drone
+    pub fn state(&self) -> DroneState {
+        self.inner
+            .state
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Subscribes to the incoming frames that match the `Filter`.
+    ///
+    /// Up to `subscription::DEFAULT_CAPACITY` frames are buffered,
+    /// after that the newest frames are dropped until the `Subscription` catches up.
+    pub fn subscribe(&self, filter: Filter) -> Subscription {
+        self.subscribe_with(filter, subscription::DEFAULT_CAPACITY, Policy::default())
+    }
+
+    /// Subscribes to the incoming frames that match the `Filter`
+    /// with a custom buffer `capacity` and `Policy` for when the buffer is full.
+    ///
+    /// The `capacity` is at least `subscription::MIN_CAPACITY`.
+    pub fn subscribe_with(&self, filter: Filter, capacity: usize, policy: Policy) -> Subscription {
+        self.inner.subscribers.subscribe(filter, capacity, policy)
     }
 
     pub fn send_raw_message(&self, raw_message: &[u8]) -> Result<(), Error> {
-        Ok(self.inner.sender.send(raw_message.to_vec())?)
+        self.inner.sender.send(raw_message.to_vec())
     }
 
     pub fn send_datetime(&self, date: DateTime<Utc>) -> Result<(), Error> {
@@ -181,7 +413,27 @@ impl Drone {
             Some(time_feature),
         );
 
-        self.send_frame(frame)
+        self.send_frame(frame)?;
+
+        Ok(())
+    }
+
+    /// PINGs the Drone with our timestamp, which it echoes back in a PONG for `LinkStats::rtt`
+    fn send_ping(&self) -> Result<(), Error> {
+        let frame_type = frame::Type::Data;
+        let buffer_id = frame::BufferID::PING;
+
+        let mut payload = self.inner.link_stats.ping_payload(Instant::now());
+        // the PING data is parsed as an unknown feature
+        let feature = command::Feature::Unknown {
+            feature: payload.remove(0),
+            data: payload,
+        };
+        let ping = frame::Frame::for_drone(self, frame_type, buffer_id, Some(feature));
+
+        self.send_frame(ping)?;
+
+        Ok(())
     }
 
     fn send_pong(&self, feature: Option<command::Feature>) -> Result<(), Error> {
@@ -191,11 +443,95 @@ impl Drone {
         // send the same feature back
         let pong = frame::Frame::for_drone(&self, frame_type, buffer_id, feature);
 
-        self.send_frame(pong)
+        self.send_frame(pong)?;
+
+        Ok(())
+    }
+
+    /// Called when the Drone acknowledges a `Type::DataWithAck` frame we've sent
+    fn ack_received(&self, buffer_id: frame::BufferID, sequence_id: u8) {
+        if !self.inner.pending_acks.acknowledge(buffer_id, sequence_id) {
+            info!(
+                "Ack for {} with sequence id {} doesn't match any sent frame",
+                buffer_id, sequence_id
+            );
+        }
+    }
+}
+
+/// How many frames can wait in the channel of the Command sender
+const COMMANDS_CAPACITY: usize = 200;
+
+/// The sending side of the channel to the Command sender
+#[derive(Debug)]
+enum CommandSender {
+    Blocking(SyncSender<Vec<u8>>),
+    #[cfg(feature = "tokio")]
+    Tokio(tokio::sync::mpsc::Sender<Vec<u8>>),
+}
+
+impl CommandSender {
+    /// Blocks if the channel is full, except for the `tokio` sender which returns an error instead
+    fn send(&self, raw_message: Vec<u8>) -> Result<(), Error> {
+        match self {
+            CommandSender::Blocking(sender) => Ok(sender.send(raw_message)?),
+            #[cfg(feature = "tokio")]
+            CommandSender::Tokio(sender) => {
+                use tokio::sync::mpsc::error::TrySendError;
+
+                sender
+                    .clone()
+                    .try_send(raw_message)
+                    .map_err(|err| match err {
+                        TrySendError::Full(raw_message) | TrySendError::Closed(raw_message) => {
+                            Error::Send(SendError(raw_message))
+                        }
+                    })
+            }
+        }
+    }
+
+    /// Waits if the channel is full, the `Blocking` sender blocks the current thread instead
+    #[cfg(feature = "tokio")]
+    async fn send_async(&self, raw_message: Vec<u8>) -> Result<(), Error> {
+        match self {
+            CommandSender::Blocking(_) => self.send(raw_message),
+            CommandSender::Tokio(sender) => sender
+                .clone()
+                .send(raw_message)
+                .await
+                .map_err(|err| Error::Send(SendError(err.0))),
+        }
     }
 }
 
 impl DroneInner {
+    fn disconnect(&self) {
+        if self.disconnected.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        info!("Disconnecting Drone");
+        self.pending_acks.cancel();
+
+        join_threads(&self.threads);
+        join_threads(&self.link_threads);
+    }
+
+    fn add_thread(&self, thread: JoinHandle<()>) {
+        self.threads
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(thread);
+    }
+
+    fn add_link_thread(&self, thread: JoinHandle<()>) {
+        self.link_threads
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(thread);
+    }
+
     pub(crate) fn sequence_id(&self, buffer_id: frame::BufferID) -> u8 {
         if let Some(mut sequence_id) = self.sequence_ids.get_mut(&buffer_id) {
             let command_id = *sequence_id;
@@ -203,35 +539,97 @@ impl DroneInner {
 
             command_id
         } else {
-            self.sequence_ids.insert(buffer_id, 0);
+            self.sequence_ids.insert(buffer_id, 1);
             0
         }
     }
 }
 
+fn join_threads(threads: &Mutex<Vec<JoinHandle<()>>>) {
+    let threads = std::mem::take(&mut *threads.lock().unwrap_or_else(PoisonError::into_inner));
+    let current = std::thread::current().id();
+
+    // the last `Drone` might be dropped by one of the threads
+    for thread in threads.into_iter().filter(|t| t.thread().id() != current) {
+        if thread.join().is_err() {
+            error!("Background thread of the Drone panicked");
+        }
+    }
+}
+
+impl Drop for DroneInner {
+    fn drop(&mut self) {
+        self.disconnect();
+    }
+}
+
+/// The local ip to bind to, in order of preference:
+///
+/// * `Config::local_addr`
+/// * The ip of the interface that is in the same network as the Drone (`pnet` feature)
+/// * The ip of the interface that the OS routes the Drone's traffic through
+fn local_ip(config: &Config) -> Option<IpAddr> {
+    config
+        .local_addr
+        .or_else(|| interface_ip(config.drone_addr))
+        .or_else(|| routed_ip(config.drone_addr))
+}
+
 // returns ip of the interface that is in the same network as the target
-fn local_ip(target: IpAddr) -> Option<IpAddr> {
-    datalink::interfaces()
+#[cfg(feature = "pnet")]
+fn interface_ip(target: IpAddr) -> Option<IpAddr> {
+    pnet::datalink::interfaces()
         .into_iter()
         .filter_map(|interface| interface.ips.into_iter().find(|ip| ip.contains(target)))
         .map(|ip_network| ip_network.ip())
         .next()
 }
 
-fn spawn_listener(drone: Drone, addr: SocketAddr) -> Result<(), ConnectionError> {
+#[cfg(not(feature = "pnet"))]
+fn interface_ip(_target: IpAddr) -> Option<IpAddr> {
+    None
+}
+
+/// Connecting an UDP socket doesn't send anything,
+/// but it picks the local ip that the OS would use for sending to the target
+fn routed_ip(target: IpAddr) -> Option<IpAddr> {
+    let unspecified = match target {
+        IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+    };
+
+    let socket = UdpSocket::bind(SocketAddr::new(unspecified, 0)).ok()?;
+    socket.connect(SocketAddr::new(target, INIT_PORT)).ok()?;
+
+    socket
+        .local_addr()
+        .ok()
+        .map(|local_addr| local_addr.ip())
+        .filter(|ip| !ip.is_unspecified())
+}
+
+/// Returns the bound port, e.g. the free port that was picked for port `0`
+fn spawn_listener(drone: &Drone, addr: SocketAddr) -> Result<u16, ConnectionError> {
     let listener_socket =
         UdpSocket::bind(addr).map_err(|error| ConnectionError::Io { error, addr })?;
+    listener_socket
+        .set_read_timeout(Some(SHUTDOWN_POLL_INTERVAL))
+        .map_err(|error| ConnectionError::Io { error, addr })?;
+    let d2c_port = listener_socket
+        .local_addr()
+        .map_err(|error| ConnectionError::Io { error, addr })?
+        .port();
+
+    let listener = Listener {
+        drone: drone.downgrade_link(),
+        socket: listener_socket,
+    };
 
-    std::thread::spawn(move || {
-        let listener = Listener {
-            drone: drone.clone(),
-            socket: listener_socket,
-        };
-
-        listener.listen();
-    });
+    drone
+        .inner
+        .add_link_thread(std::thread::spawn(move || listener.listen()));
 
-    Ok(())
+    Ok(d2c_port)
 }
 
 pub(crate) fn print_buf(buf: &[u8]) -> String {
@@ -242,47 +640,160 @@ pub(crate) fn print_buf(buf: &[u8]) -> String {
 }
 
 fn spawn_cmd_sender(
-    rx: Receiver<Vec<u8>>,
+    drone: &Drone,
+    commands: Arc<Mutex<Receiver<Vec<u8>>>>,
     local_ip: IpAddr,
     target_addr: SocketAddr,
 ) -> Result<(), ConnectionError> {
-    let local_addr = SocketAddr::new(local_ip, target_addr.port());
+    // any free port, so multiple Drones with the same `c2d_port` can be connected
+    let local_addr = SocketAddr::new(local_ip, 0);
 
     let socket = UdpSocket::bind(local_addr).map_err(|error| ConnectionError::Io {
         error,
         addr: local_addr,
     })?;
 
-    std::thread::spawn(move || loop {
-        let frame_to_send = match rx.recv() {
-            Ok(frame) => frame,
-            Err(err) => {
-                error!("Receiving Frame for sending failed: {:?}", &err);
-                continue;
+    let weak_drone = drone.downgrade_link();
+    let recorder = drone.inner.recorder.clone();
+
+    let thread = std::thread::spawn(move || {
+        let rx = commands.lock().unwrap_or_else(PoisonError::into_inner);
+
+        loop {
+            let received = rx.recv_timeout(SHUTDOWN_POLL_INTERVAL);
+
+            // checked before every frame too, or a steady stream of frames would keep us running
+            // after the Drone was disconnected, dropped or while we're reconnecting
+            if weak_drone.upgrade().is_none() {
+                break;
             }
-        };
 
-        info!("Frame to sent: {:?}", &frame_to_send);
+            let frame_to_send = match received {
+                Ok(frame) => frame,
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+
+            info!("Frame to sent: {:?}", &frame_to_send);
+
+            let frame = frame_to_send.pread_with::<Frame>(0, LE);
+
+            info!(
+                "Sent Frame (length: {}) => {:?}",
+                frame_to_send.len(),
+                &frame
+            );
+
+            match socket.send_to(&frame_to_send, target_addr) {
+                Ok(size) if size == frame_to_send.len() => {
+                    record::record(recorder.as_deref(), Direction::Outgoing, &frame_to_send)
+                }
+                Ok(size) => error!(
+                    "Sent only {} out of {} bytes of the Frame",
+                    size,
+                    frame_to_send.len()
+                ),
+                Err(err) => error!("Sending Frame to Drone: {}", err),
+            }
+        }
+    });
 
-        let frame = frame_to_send.pread_with::<Frame>(0, LE);
+    drone.inner.add_link_thread(thread);
 
-        info!(
-            "Sent Frame (length: {}) => {:?}",
-            frame_to_send.len(),
-            &frame
-        );
+    Ok(())
+}
 
-        let size = socket
-            .send_to(&frame_to_send, target_addr)
-            .expect("something terrible happened");
+fn spawn_ack_retransmitter(drone: &Drone) {
+    let tick = drone.inner.pending_acks.check_interval();
+    let weak_drone = drone.downgrade();
 
-        assert_eq!(size, frame_to_send.len())
+    let thread = std::thread::spawn(move || loop {
+        std::thread::sleep(tick);
+
+        let drone = match weak_drone.upgrade() {
+            Some(drone) => drone,
+            None => break,
+        };
+
+        for raw_message in drone.inner.pending_acks.retransmissions(Instant::now()) {
+            info!("Re-sending Frame: {}", print_buf(&raw_message));
+
+            if let Err(err) = drone.send_raw_message(&raw_message) {
+                error!("Re-sending Frame to Commander: {}", err)
+            }
+        }
     });
 
-    Ok(())
+    drone.inner.add_thread(thread);
 }
 
-/// we receive 2 frames sometimes
+fn spawn_link_supervisor(drone: &Drone, connection: Connection) {
+    let reconnect = connection.config.reconnect;
+    let weak_drone = drone.downgrade();
+
+    let thread = std::thread::spawn(move || {
+        let mut failed_attempts = 0;
+        let mut next_attempt = Instant::now();
+        let mut last_ping = Instant::now();
+
+        loop {
+            std::thread::sleep(SHUTDOWN_POLL_INTERVAL);
+
+            let drone = match weak_drone.upgrade() {
+                Some(drone) => drone,
+                None => break,
+            };
+
+            let now = Instant::now();
+            match drone.inner.link.update(now) {
+                Some(LinkState::Lost) => {
+                    warn!("Link to the Drone is lost");
+                    failed_attempts = 0;
+                    next_attempt = now;
+                }
+                Some(link_state) => info!("Link to the Drone is {:?}", link_state),
+                None => {}
+            }
+
+            if now.saturating_duration_since(last_ping) >= link::PING_INTERVAL {
+                last_ping = now;
+
+                if let Err(err) = drone.send_ping() {
+                    error!("Sending PING to Commander: {}", err)
+                }
+            }
+
+            let backoff = match reconnect {
+                Some(backoff)
+                    if drone.inner.link.state(now) == LinkState::Lost
+                        && now >= next_attempt
+                        && backoff.should_retry(failed_attempts) =>
+                {
+                    backoff
+                }
+                _ => continue,
+            };
+
+            info!("Reconnecting to the Drone, attempt {}", failed_attempts + 1);
+
+            drone.close_link();
+            match drone.open_link(&connection) {
+                Ok(()) => info!("Reconnected to the Drone"),
+                Err(err) => {
+                    failed_attempts += 1;
+                    next_attempt = Instant::now() + backoff.delay(failed_attempts);
+
+                    error!("Reconnecting to the Drone: {}", err);
+                    if !backoff.should_retry(failed_attempts) {
+                        error!("Giving up reconnecting after {} attempts", failed_attempts);
+                    }
+                }
+            }
+        }
+    });
+
+    drone.inner.add_thread(thread);
+}
 
 #[cfg(test)]
 mod test {
@@ -292,6 +803,7 @@ mod test {
     use command::Feature;
     use frame::{BufferID, Type};
 
+    // we receive 2 frames sometimes
     #[test]
     fn receiving_two_frames_at_once() {
         let received: [u8; 58] = [
@@ -307,7 +819,8 @@ mod test {
             // Frame size: 35
             // ArDrone3 - Feature - 1
             // Piloting State - 4
-            // Data?
+            // PositionChanged - 4
+            // latitude, longitude, altitude: 500.0 (not available)
             2, 127, 71, 35, 0, 0, 0, 1, 4, 4, 0, 0, 0, 0, 0, 0, 64, 127, 64, 0, 0, 0, 0, 0, 64, 127,
             64, 0, 0, 0, 0, 0, 64, 127, 64,
         ];
@@ -333,9 +846,10 @@ mod test {
             buffer_id: BufferID::DCNavdata,
             sequence_id: 71,
             feature: Some(Feature::ArDrone3(Some(ArDrone3::PilotingState(
-                ardrone3::PilotingState::Unknown {
-                    piloting_state: 4,
-                    data: received[34..].to_vec(),
+                ardrone3::PilotingState::PositionChanged {
+                    latitude: 500.0,
+                    longitude: 500.0,
+                    altitude: 500.0,
                 },
             )))),
         };
@@ -354,6 +868,118 @@ mod test {
         );
     }
 
+    fn free_local_addr() -> SocketAddr {
+        UdpSocket::bind("127.0.0.1:0")
+            .and_then(|socket| socket.local_addr())
+            .expect("Should bind to a free port")
+    }
+
+    #[test]
+    fn local_ip_fallbacks() {
+        let config = Config::builder(Ipv4Addr::LOCALHOST)
+            .local_addr([192, 168, 42, 10])
+            .build()
+            .expect("Should be a valid Config");
+        assert_eq!(Some(IpAddr::from([192, 168, 42, 10])), local_ip(&config));
+
+        assert_eq!(
+            Some(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            routed_ip(Ipv4Addr::LOCALHOST.into())
+        );
+    }
+
+    #[test]
+    fn disconnect_frees_the_listener_port() {
+        let (tx_cmd, _rx_cmd) = sync_channel(COMMANDS_CAPACITY);
+        let drone = Drone::new(CommandSender::Blocking(tx_cmd), &PARROT_SPHINX_CONFIG);
+        let addr = free_local_addr();
+
+        spawn_listener(&drone, addr).expect("Should spawn listener");
+        spawn_ack_retransmitter(&drone);
+
+        let ack = drone
+            .send_frame(Frame::for_drone(
+                &drone,
+                Type::DataWithAck,
+                BufferID::CDAck,
+                None,
+            ))
+            .expect("Should send frame");
+
+        drone.disconnect();
+
+        assert!(drone.is_disconnected());
+        assert!(matches!(ack.wait(), Err(Error::Disconnected)));
+        assert!(UdpSocket::bind(addr).is_ok());
+    }
+
+    #[test]
+    fn frame_that_failed_to_send_is_not_resent() {
+        let (tx_cmd, rx_cmd) = sync_channel(COMMANDS_CAPACITY);
+        let drone = Drone::new(CommandSender::Blocking(tx_cmd), &PARROT_SPHINX_CONFIG);
+        drop(rx_cmd);
+
+        let result = drone.send_frame(Frame::for_drone(
+            &drone,
+            Type::DataWithAck,
+            BufferID::CDAck,
+            None,
+        ));
+
+        assert!(matches!(result, Err(Error::Send(_))));
+        let later = Instant::now() + PARROT_SPHINX_CONFIG.ack_timeout * 2;
+        assert!(drone.inner.pending_acks.retransmissions(later).is_empty());
+    }
+
+    #[test]
+    fn closing_the_link_stops_a_busy_cmd_sender() {
+        let (tx_cmd, rx_cmd) = sync_channel(COMMANDS_CAPACITY);
+        let drone = Drone::new(CommandSender::Blocking(tx_cmd), &PARROT_SPHINX_CONFIG);
+
+        spawn_cmd_sender(
+            &drone,
+            Arc::new(Mutex::new(rx_cmd)),
+            Ipv4Addr::LOCALHOST.into(),
+            free_local_addr(),
+        )
+        .expect("Should spawn cmd sender");
+
+        // more often than `SHUTDOWN_POLL_INTERVAL`, until the cmd sender drops the commands
+        let sending = std::thread::spawn({
+            let drone = drone.clone();
+            move || {
+                while drone.send_raw_message(&[2, 10, 0, 7, 0, 0, 0]).is_ok() {
+                    std::thread::sleep(Duration::from_millis(5));
+                }
+            }
+        });
+
+        let (closed_tx, closed) = sync_channel(1);
+        std::thread::spawn(move || {
+            drone.close_link();
+            closed_tx.send(()).expect("Should notify");
+        });
+
+        assert!(closed.recv_timeout(SHUTDOWN_POLL_INTERVAL * 10).is_ok());
+        sending.join().expect("Should stop sending");
+    }
+
+    #[test]
+    fn dropping_the_last_drone_frees_the_listener_port() {
+        let (tx_cmd, _rx_cmd) = sync_channel(COMMANDS_CAPACITY);
+        let drone = Drone::new(CommandSender::Blocking(tx_cmd), &PARROT_SPHINX_CONFIG);
+        let addr = free_local_addr();
+
+        spawn_listener(&drone, addr).expect("Should spawn listener");
+
+        let clone = drone.clone();
+        drop(drone);
+        assert!(UdpSocket::bind(addr).is_err());
+
+        drop(clone);
+        assert!(UdpSocket::bind(addr).is_ok());
+    }
+
     #[test]
     #[ignore]
     fn receiving_two_frames_at_once_2() {
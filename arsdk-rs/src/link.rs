@@ -0,0 +1,432 @@
+use crate::frame::BufferID;
+use scroll::{Pread, Pwrite, LE};
+use std::{
+    collections::HashMap,
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Mutex, MutexGuard, PoisonError,
+    },
+    time::{Duration, Instant},
+};
+
+/// The Drone PINGs us a few times per second, so a second without any frame is suspicious
+pub const DEFAULT_DEGRADED_AFTER: Duration = Duration::from_secs(1);
+pub const DEFAULT_LOST_AFTER: Duration = Duration::from_secs(5);
+/// How often we PING the Drone to measure the round-trip time
+pub(crate) const PING_INTERVAL: Duration = Duration::from_secs(1);
+/// Window of the bytes and frames per second rates
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+pub const DEFAULT_BACKOFF: Backoff = Backoff {
+    initial: Duration::from_millis(500),
+    max: Duration::from_secs(30),
+    factor: 2,
+    max_attempts: None,
+};
+
+/// Quality of the link to the Drone based on the time since the last received frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkState {
+    /// Frames are received regularly
+    Connected,
+    /// No frame was received for `Config::degraded_after`
+    Degraded,
+    /// No frame was received for `Config::lost_after`
+    Lost,
+}
+
+/// How often to retry the reconnecting to a lost Drone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    /// Delay after the first failed attempt
+    pub initial: Duration,
+    /// The delay is never longer than this
+    pub max: Duration,
+    /// The delay is multiplied by it after each failed attempt
+    pub factor: u32,
+    /// Stop reconnecting after that many failed attempts, `None` for trying forever
+    pub max_attempts: Option<u32>,
+}
+
+impl Backoff {
+    /// Delay before the next attempt after `failed_attempts` attempts
+    pub fn delay(&self, failed_attempts: u32) -> Duration {
+        let factor = self
+            .factor
+            .checked_pow(failed_attempts.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+
+        self.initial
+            .checked_mul(factor)
+            .map_or(self.max, |delay| delay.min(self.max))
+    }
+
+    pub fn should_retry(&self, failed_attempts: u32) -> bool {
+        self.max_attempts
+            .map_or(true, |max_attempts| failed_attempts < max_attempts)
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        DEFAULT_BACKOFF
+    }
+}
+
+#[derive(Debug)]
+struct Link {
+    last_received: Instant,
+    state: LinkState,
+    subscribers: Vec<Sender<LinkState>>,
+}
+
+/// Keeps track of the time since the last received frame
+#[derive(Debug)]
+pub(crate) struct LinkMonitor {
+    degraded_after: Duration,
+    lost_after: Duration,
+    link: Mutex<Link>,
+}
+
+impl LinkMonitor {
+    pub(crate) fn new(degraded_after: Duration, lost_after: Duration) -> Self {
+        Self {
+            degraded_after,
+            lost_after,
+            link: Mutex::new(Link {
+                // give the Drone some time to start sending frames
+                last_received: Instant::now(),
+                state: LinkState::Connected,
+                subscribers: Vec::new(),
+            }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Link> {
+        self.link.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Should be called for every datagram received from the Drone
+    pub(crate) fn received(&self) {
+        self.lock().last_received = Instant::now();
+    }
+
+    pub(crate) fn state(&self, now: Instant) -> LinkState {
+        let elapsed = now.saturating_duration_since(self.lock().last_received);
+
+        if elapsed >= self.lost_after {
+            LinkState::Lost
+        } else if elapsed >= self.degraded_after {
+            LinkState::Degraded
+        } else {
+            LinkState::Connected
+        }
+    }
+
+    /// Notifies the subscribers if the `LinkState` has changed and returns the new `LinkState`
+    pub(crate) fn update(&self, now: Instant) -> Option<LinkState> {
+        let state = self.state(now);
+        let mut link = self.lock();
+
+        if link.state == state {
+            return None;
+        }
+
+        link.state = state;
+        link.subscribers
+            .retain(|subscriber| subscriber.send(state).is_ok());
+
+        Some(state)
+    }
+
+    pub(crate) fn subscribe(&self) -> Receiver<LinkState> {
+        let (sender, receiver) = channel();
+        self.lock().subscribers.push(sender);
+
+        receiver
+    }
+}
+
+/// Quality metrics of the link to the Drone, see `Drone::link_stats()`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LinkStats {
+    /// Stats of the incoming frames for each `BufferID`
+    pub buffers: HashMap<BufferID, BufferStats>,
+    /// Round-trip time of the last PING that the Drone answered with a PONG
+    pub rtt: Option<Duration>,
+    /// Total bytes received from the Drone
+    pub bytes_received: u64,
+    /// Total frames received from the Drone
+    pub frames_received: u64,
+    /// Over the last full second
+    pub bytes_per_second: f64,
+    /// Over the last full second
+    pub frames_per_second: f64,
+}
+
+/// Stats of the incoming frames of a single `BufferID`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BufferStats {
+    pub frames: u64,
+    /// Frames that we've never received, based on the skipped sequence ids
+    pub missed: u64,
+    /// Frames with the same sequence id as the last one or an older one
+    pub duplicates: u64,
+}
+
+#[derive(Debug)]
+struct Counters {
+    stats: LinkStats,
+    last_sequence_ids: HashMap<BufferID, u8>,
+    window_start: Instant,
+    window_bytes: u64,
+    window_frames: u64,
+}
+
+impl Counters {
+    fn roll_window(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.window_start);
+        if elapsed < RATE_WINDOW {
+            return;
+        }
+
+        let seconds = elapsed.as_secs_f64();
+        // nothing was received in the last full window
+        let (bytes, frames) = if elapsed >= RATE_WINDOW * 2 {
+            (0, 0)
+        } else {
+            (self.window_bytes, self.window_frames)
+        };
+
+        self.stats.bytes_per_second = bytes as f64 / seconds;
+        self.stats.frames_per_second = frames as f64 / seconds;
+        self.window_start = now;
+        self.window_bytes = 0;
+        self.window_frames = 0;
+    }
+}
+
+/// Collects the `LinkStats` from the incoming frames and the PONGs of our PINGs
+#[derive(Debug)]
+pub(crate) struct LinkStatsTracker {
+    /// The PING timestamps are relative to it
+    started: Instant,
+    counters: Mutex<Counters>,
+}
+
+impl LinkStatsTracker {
+    pub(crate) fn new() -> Self {
+        let started = Instant::now();
+
+        Self {
+            started,
+            counters: Mutex::new(Counters {
+                stats: LinkStats::default(),
+                last_sequence_ids: HashMap::new(),
+                window_start: started,
+                window_bytes: 0,
+                window_frames: 0,
+            }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Counters> {
+        self.counters.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Should be called for every datagram received from the Drone
+    pub(crate) fn received(&self, now: Instant, bytes: usize, frames: usize) {
+        let mut counters = self.lock();
+        counters.roll_window(now);
+
+        counters.stats.bytes_received += bytes as u64;
+        counters.stats.frames_received += frames as u64;
+        counters.window_bytes += bytes as u64;
+        counters.window_frames += frames as u64;
+    }
+
+    /// Counts the skipped and repeated sequence ids, they wrap around after 255
+    pub(crate) fn sequence_id_received(&self, buffer_id: BufferID, sequence_id: u8) {
+        let mut counters = self.lock();
+        let last_sequence_id = counters.last_sequence_ids.get(&buffer_id).copied();
+        let buffer = counters.stats.buffers.entry(buffer_id).or_default();
+
+        buffer.frames += 1;
+
+        match last_sequence_id.map(|last| sequence_id.wrapping_sub(last)) {
+            // the same or an older sequence id
+            Some(difference) if difference == 0 || difference >= 128 => {
+                buffer.duplicates += 1;
+                return;
+            }
+            Some(difference) => buffer.missed += u64::from(difference - 1),
+            None => {}
+        }
+
+        counters.last_sequence_ids.insert(buffer_id, sequence_id);
+    }
+
+    /// Forgets the last sequence ids, e.g. the Drone restarts them when we reconnect
+    pub(crate) fn clear_sequence_ids(&self) {
+        self.lock().last_sequence_ids.clear();
+    }
+
+    /// The timestamp to send in a PING, as the seconds and nanoseconds of a `timespec`
+    pub(crate) fn ping_payload(&self, now: Instant) -> Vec<u8> {
+        let timestamp = now.saturating_duration_since(self.started);
+        let mut payload = vec![0_u8; 16];
+
+        payload
+            .pwrite_with::<i64>(timestamp.as_secs() as i64, 0, LE)
+            .expect("Should fit the seconds");
+        payload
+            .pwrite_with::<i64>(timestamp.subsec_nanos().into(), 8, LE)
+            .expect("Should fit the nanoseconds");
+
+        payload
+    }
+
+    /// Updates the round-trip time from the timestamp of our PING echoed back in the PONG
+    pub(crate) fn pong_received(&self, now: Instant, payload: &[u8]) {
+        let timestamp = payload.pread_with::<i64>(0, LE).and_then(|seconds| {
+            let nanos = payload.pread_with::<i64>(8, LE)?;
+
+            Ok((seconds, nanos))
+        });
+
+        let sent_at = match timestamp {
+            Ok((seconds, nanos)) if seconds >= 0 && (0..1_000_000_000).contains(&nanos) => {
+                Duration::new(seconds as u64, nanos as u32)
+            }
+            _ => return,
+        };
+
+        if let Some(rtt) = now
+            .saturating_duration_since(self.started)
+            .checked_sub(sent_at)
+        {
+            self.lock().stats.rtt = Some(rtt);
+        }
+    }
+
+    pub(crate) fn stats(&self, now: Instant) -> LinkStats {
+        let mut counters = self.lock();
+        counters.roll_window(now);
+
+        counters.stats.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_link_state_changes() {
+        let monitor = LinkMonitor::new(Duration::from_secs(1), Duration::from_secs(5));
+        let events = monitor.subscribe();
+        let start = Instant::now();
+
+        assert_eq!(None, monitor.update(start));
+        assert_eq!(
+            Some(LinkState::Degraded),
+            monitor.update(start + Duration::from_secs(2))
+        );
+        assert_eq!(None, monitor.update(start + Duration::from_secs(3)));
+        assert_eq!(
+            Some(LinkState::Lost),
+            monitor.update(start + Duration::from_secs(6))
+        );
+
+        monitor.received();
+        assert_eq!(Some(LinkState::Connected), monitor.update(Instant::now()));
+
+        assert_eq!(
+            vec![LinkState::Degraded, LinkState::Lost, LinkState::Connected],
+            events.try_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_backoff() {
+        let backoff = Backoff {
+            initial: Duration::from_millis(500),
+            max: Duration::from_secs(3),
+            factor: 2,
+            max_attempts: Some(5),
+        };
+
+        assert_eq!(Duration::from_millis(500), backoff.delay(1));
+        assert_eq!(Duration::from_secs(1), backoff.delay(2));
+        assert_eq!(Duration::from_secs(2), backoff.delay(3));
+        assert_eq!(Duration::from_secs(3), backoff.delay(4));
+        assert_eq!(Duration::from_secs(3), backoff.delay(100));
+
+        assert!(backoff.should_retry(4));
+        assert!(!backoff.should_retry(5));
+    }
+
+    #[test]
+    fn test_sequence_ids() {
+        let tracker = LinkStatsTracker::new();
+
+        for sequence_id in [254, 255, 0, 3, 3, 2, 4].iter() {
+            tracker.sequence_id_received(BufferID::DCNavdata, *sequence_id);
+        }
+        tracker.sequence_id_received(BufferID::PING, 7);
+
+        // not a duplicate after the sequence ids were restarted
+        tracker.clear_sequence_ids();
+        tracker.sequence_id_received(BufferID::PING, 0);
+
+        let stats = tracker.stats(Instant::now());
+
+        assert_eq!(
+            Some(&BufferStats {
+                frames: 7,
+                // 1 and 2
+                missed: 2,
+                // the second 3 and the late 2
+                duplicates: 2,
+            }),
+            stats.buffers.get(&BufferID::DCNavdata)
+        );
+        assert_eq!(
+            Some(&BufferStats {
+                frames: 2,
+                missed: 0,
+                duplicates: 0,
+            }),
+            stats.buffers.get(&BufferID::PING)
+        );
+    }
+
+    #[test]
+    fn test_rtt_and_rates() {
+        let tracker = LinkStatsTracker::new();
+        let start = tracker.started;
+
+        let payload = tracker.ping_payload(start + Duration::from_millis(1500));
+        assert_eq!(16, payload.len());
+
+        // not a timestamp of ours
+        tracker.pong_received(start, &[1, 2, 3]);
+        assert_eq!(None, tracker.stats(start).rtt);
+
+        tracker.pong_received(start + Duration::from_millis(1540), &payload);
+
+        tracker.received(start + Duration::from_millis(100), 100, 2);
+        tracker.received(start + Duration::from_millis(600), 50, 1);
+
+        let stats = tracker.stats(start + Duration::from_millis(1000));
+        assert_eq!(Some(Duration::from_millis(40)), stats.rtt);
+        assert_eq!(150, stats.bytes_received);
+        assert_eq!(3, stats.frames_received);
+        assert_eq!(150.0, stats.bytes_per_second);
+        assert_eq!(3.0, stats.frames_per_second);
+
+        // nothing received for a while
+        let stats = tracker.stats(start + Duration::from_secs(4));
+        assert_eq!(0.0, stats.bytes_per_second);
+        assert_eq!(150, stats.bytes_received);
+    }
+}
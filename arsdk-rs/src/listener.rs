@@ -1,23 +1,50 @@
-use crate::{parse::handle_bytes, print_buf, Drone};
-use log::debug;
-use std::net::UdpSocket;
+use crate::{
+    parse::handle_bytes,
+    print_buf,
+    record::{record, Direction},
+    WeakDrone,
+};
+use log::{debug, error};
+use std::{io::ErrorKind, net::UdpSocket};
 
 pub struct Listener {
-    pub drone: Drone,
-    pub socket: UdpSocket,
+    pub(crate) drone: WeakDrone,
+    /// Should have a read timeout, so the `Listener` can stop once the `Drone` is disconnected
+    pub(crate) socket: UdpSocket,
 }
 
 impl Listener {
-    /// Blocking listener in a infinite loop
+    /// Blocking listener in a loop until the `Drone` is disconnected or dropped
     pub fn listen(&self) {
+        let mut buf = [0_u8; 256];
+
         loop {
-            let mut buf = [0_u8; 256];
-            if let Ok((bytes_read, origin)) = self.socket.recv_from(&mut buf) {
-                debug!("Received: {} bytes from {}", bytes_read, origin);
-                debug!("Bytes: {}", print_buf(&buf[..bytes_read]));
+            match self.socket.recv_from(&mut buf) {
+                Ok((bytes_read, origin)) => {
+                    debug!("Received: {} bytes from {}", bytes_read, origin);
+                    debug!("Bytes: {}", print_buf(&buf[..bytes_read]));
 
-                handle_bytes(&self.drone, &buf[..bytes_read]);
+                    match self.drone.upgrade() {
+                        Some(drone) => {
+                            record(
+                                drone.inner.recorder.as_deref(),
+                                Direction::Incoming,
+                                &buf[..bytes_read],
+                            );
+                            handle_bytes(&drone, &buf[..bytes_read])
+                        }
+                        None => break,
+                    }
+                }
+                Err(err) if [ErrorKind::WouldBlock, ErrorKind::TimedOut].contains(&err.kind()) => {
+                    if self.drone.upgrade().is_none() {
+                        break;
+                    }
+                }
+                Err(err) => error!("Receiving from Drone: {}", err),
             }
         }
+
+        debug!("Listener stopped");
     }
 }
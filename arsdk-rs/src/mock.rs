@@ -0,0 +1,682 @@
+//! Drone side of the protocol for testing without hardware, enabled with the `mock` feature.
+//!
+//! The `MockDrone` runs on loopback:
+//!
+//! * answers the Handshake at its `init_port()` with a `handshake::Response`
+//! * acknowledges the `Type::DataWithAck` frames and records every received `Frame`
+//! * PINGs the controller and answers its PINGs with PONGs
+//! * sends the scripted events
+//! * replays the incoming datagrams of a recorded session
+//!
+//! ```ignore
+//! use arsdk_rs::{mock::MockDrone, prelude::*};
+//! use std::net::Ipv4Addr;
+//!
+//! let mock = MockDrone::builder().start()?;
+//! let config = Config::builder(Ipv4Addr::LOCALHOST)
+//!     .init_port(mock.init_port())
+//!     .build()?;
+//!
+//! let drone = Drone::connect(config)?;
+//! ```
+use crate::{
+    command::Feature,
+    frame::{BufferID, Frame, FrameType, Type},
+    parse::parse_message_frames,
+    print_buf,
+    record::{Direction, Record},
+};
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use log::{debug, error, info};
+use scroll::{ctx::TryIntoCtx, LE};
+use std::{
+    io::{self, ErrorKind, Read, Write},
+    net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener, TcpStream, UdpSocket},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+/// How often the `MockDrone` threads check if it was stopped
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+/// The Drones PING a few times per second
+pub const DEFAULT_PING_INTERVAL: Duration = Duration::from_millis(500);
+
+/// An event sent by the `MockDrone` as a `Type::DataWithAck` frame on `BufferID::DCEvent`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptedEvent {
+    /// Time after the Handshake
+    pub after: Duration,
+    pub feature: Feature,
+}
+
+#[derive(Debug, Clone)]
+pub struct MockDroneBuilder {
+    ip: IpAddr,
+    init_port: u16,
+    ping_interval: Option<Duration>,
+    script: Vec<ScriptedEvent>,
+    ack: bool,
+}
+
+impl Default for MockDroneBuilder {
+    fn default() -> Self {
+        Self {
+            ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            // any free port
+            init_port: 0,
+            ping_interval: Some(DEFAULT_PING_INTERVAL),
+            script: Vec::new(),
+            ack: true,
+        }
+    }
+}
+
+impl MockDroneBuilder {
+    pub fn ip(mut self, ip: impl Into<IpAddr>) -> Self {
+        self.ip = ip.into();
+        self
+    }
+
+    /// `0` for any free port, see `MockDrone::init_port()`
+    pub fn init_port(mut self, init_port: u16) -> Self {
+        self.init_port = init_port;
+        self
+    }
+
+    /// `None` for not PINGing the controller, e.g. for testing a lost link
+    pub fn ping_interval(mut self, ping_interval: Option<Duration>) -> Self {
+        self.ping_interval = ping_interval;
+        self
+    }
+
+    /// Sends the `feature` at `after` the Handshake
+    pub fn event(mut self, after: Duration, feature: Feature) -> Self {
+        self.script.push(ScriptedEvent { after, feature });
+        self
+    }
+
+    /// Whether to acknowledge the `Type::DataWithAck` frames, e.g. `false` for testing the re-sending
+    pub fn ack(mut self, ack: bool) -> Self {
+        self.ack = ack;
+        self
+    }
+
+    pub fn start(self) -> io::Result<MockDrone> {
+        let tcp_listener = TcpListener::bind(SocketAddr::new(self.ip, self.init_port))?;
+        tcp_listener.set_nonblocking(true)?;
+
+        let socket = UdpSocket::bind(SocketAddr::new(self.ip, 0))?;
+        socket.set_read_timeout(Some(POLL_INTERVAL))?;
+
+        let inner = Arc::new(MockInner {
+            init_addr: tcp_listener.local_addr()?,
+            c2d_addr: socket.local_addr()?,
+            socket,
+            stopped: AtomicBool::new(false),
+            controller: Mutex::new(None),
+            received: Mutex::new(Vec::new()),
+            sequence_ids: DashMap::new(),
+        });
+
+        let handshakes = {
+            let inner = inner.clone();
+            std::thread::spawn(move || inner.accept_handshakes(tcp_listener))
+        };
+        let frames = {
+            let inner = inner.clone();
+            std::thread::spawn(move || inner.handle_frames(self))
+        };
+
+        Ok(MockDrone {
+            inner,
+            threads: vec![handshakes, frames],
+        })
+    }
+}
+
+/// The controller that made the last Handshake
+#[derive(Debug, Clone, Copy)]
+struct Controller {
+    d2c_addr: SocketAddr,
+    connected_at: Instant,
+}
+
+#[derive(Debug)]
+struct MockInner {
+    init_addr: SocketAddr,
+    c2d_addr: SocketAddr,
+    socket: UdpSocket,
+    stopped: AtomicBool,
+    controller: Mutex<Option<Controller>>,
+    received: Mutex<Vec<Frame>>,
+    sequence_ids: DashMap<BufferID, u8>,
+}
+
+/// Stopped when dropped
+#[derive(Debug)]
+pub struct MockDrone {
+    inner: Arc<MockInner>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl MockDrone {
+    pub fn builder() -> MockDroneBuilder {
+        MockDroneBuilder::default()
+    }
+
+    /// The TCP port of the Handshake, use it for `Config::init_port`
+    pub fn init_port(&self) -> u16 {
+        self.inner.init_addr.port()
+    }
+
+    /// The UDP port that is sent as `c2d_port` in the Handshake
+    pub fn c2d_port(&self) -> u16 {
+        self.inner.c2d_addr.port()
+    }
+
+    /// Whether a controller has made the Handshake
+    pub fn is_connected(&self) -> bool {
+        self.inner.controller().is_some()
+    }
+
+    /// All the frames received from the controller so far, including the PINGs and PONGs
+    pub fn received_frames(&self) -> Vec<Frame> {
+        self.inner
+            .received
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Sends a frame to the controller with the next sequence id of the `buffer_id`
+    pub fn send(
+        &self,
+        frame_type: Type,
+        buffer_id: BufferID,
+        feature: Option<Feature>,
+    ) -> io::Result<()> {
+        self.inner.send(frame_type, buffer_id, feature)
+    }
+
+    /// Sends the `feature` as a `Type::DataWithAck` frame on `BufferID::DCEvent`
+    pub fn send_event(&self, feature: Feature) -> io::Result<()> {
+        self.send(Type::DataWithAck, BufferID::DCEvent, Some(feature))
+    }
+}
+
+impl MockDrone {
+    /// Sends the `Direction::Incoming` datagrams of a recorded session to the controller,
+    /// keeping the original time between them.
+    pub fn replay(&self, records: impl IntoIterator<Item = Record>) -> io::Result<()> {
+        let controller = self
+            .inner
+            .controller()
+            .ok_or_else(|| io::Error::new(ErrorKind::NotConnected, "No Handshake yet"))?;
+        let mut previous: Option<DateTime<Utc>> = None;
+
+        for record in records
+            .into_iter()
+            .filter(|record| record.direction == Direction::Incoming)
+        {
+            if let Some(delay) =
+                previous.and_then(|previous| (record.timestamp - previous).to_std().ok())
+            {
+                std::thread::sleep(delay);
+            }
+            previous = Some(record.timestamp);
+
+            self.inner
+                .socket
+                .send_to(&record.data, controller.d2c_addr)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for MockDrone {
+    fn drop(&mut self) {
+        self.inner.stopped.store(true, Ordering::SeqCst);
+
+        for thread in self.threads.drain(..) {
+            if thread.join().is_err() {
+                error!("MockDrone thread panicked");
+            }
+        }
+    }
+}
+
+impl MockInner {
+    fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst)
+    }
+
+    fn controller(&self) -> Option<Controller> {
+        *self
+            .controller
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn accept_handshakes(&self, tcp_listener: TcpListener) {
+        while !self.is_stopped() {
+            match tcp_listener.accept() {
+                Ok((stream, origin)) => {
+                    if let Err(err) = self.handshake(stream, origin) {
+                        error!("MockDrone Handshake with {}: {}", origin, err);
+                    }
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => {
+                    std::thread::sleep(POLL_INTERVAL)
+                }
+                Err(err) => error!("MockDrone accepting Handshake: {}", err),
+            }
+        }
+    }
+
+    fn handshake(&self, mut stream: TcpStream, origin: SocketAddr) -> io::Result<()> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+
+        let mut buf = [0_u8; 512];
+        let read = stream.read(&mut buf)?;
+        let request: serde_json::Value = serde_json::from_slice(&buf[..read])?;
+        info!("MockDrone Handshake request: {}", request);
+
+        let d2c_port = request["d2c_port"]
+            .as_u64()
+            .filter(|port| *port <= u64::from(u16::MAX))
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidData, "Missing d2c_port"))?;
+
+        let response = serde_json::json!({
+            "status": 0,
+            "c2d_port": self.c2d_addr.port(),
+            "c2d_update_port": 51,
+            "c2d_user_port": 21,
+            "qos_mode": 0,
+            "arstream2_server_stream_port": 5004,
+            "arstream2_server_control_port": 5005,
+        });
+        // the Drones terminate the response with a `\0`
+        let mut response = serde_json::to_vec(&response)?;
+        response.push(0);
+
+        // before responding, the controller starts sending as soon as it gets the response
+        *self
+            .controller
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(Controller {
+            d2c_addr: SocketAddr::new(origin.ip(), d2c_port as u16),
+            connected_at: Instant::now(),
+        });
+        // a new controller starts with new sequence ids
+        self.sequence_ids.clear();
+
+        stream.write_all(&response)?;
+
+        Ok(())
+    }
+
+    fn handle_frames(&self, builder: MockDroneBuilder) {
+        let mut buf = [0_u8; 2048];
+        let mut last_ping = Instant::now();
+        let mut script = builder.script;
+        script.sort_by_key(|event| event.after);
+        let mut next_event = 0;
+        let mut scripted_for = None;
+
+        while !self.is_stopped() {
+            match self.socket.recv_from(&mut buf) {
+                Ok((read, _origin)) => self.handle_bytes(&buf[..read], builder.ack),
+                Err(err) if [ErrorKind::WouldBlock, ErrorKind::TimedOut].contains(&err.kind()) => {}
+                Err(err) => error!("MockDrone receiving: {}", err),
+            }
+
+            let controller = match self.controller() {
+                Some(controller) => controller,
+                None => continue,
+            };
+
+            // restart the script for every Handshake
+            if scripted_for != Some(controller.connected_at) {
+                scripted_for = Some(controller.connected_at);
+                next_event = 0;
+            }
+
+            while let Some(event) = script.get(next_event) {
+                if controller.connected_at.elapsed() < event.after {
+                    break;
+                }
+                next_event += 1;
+
+                if let Err(err) = self.send(
+                    Type::DataWithAck,
+                    BufferID::DCEvent,
+                    Some(event.feature.clone()),
+                ) {
+                    error!("MockDrone sending scripted event: {}", err);
+                }
+            }
+
+            if let Some(ping_interval) = builder.ping_interval {
+                if last_ping.elapsed() >= ping_interval {
+                    last_ping = Instant::now();
+
+                    if let Err(err) = self.send_ping() {
+                        error!("MockDrone sending PING: {}", err);
+                    }
+                }
+            }
+        }
+    }
+
+    fn handle_bytes(&self, raw_frames: &[u8], ack: bool) {
+        debug!("MockDrone received: {}", print_buf(raw_frames));
+
+        for result in parse_message_frames(raw_frames) {
+            let frame = match result {
+                Ok(FrameType::Known(frame)) => frame,
+                Ok(FrameType::Unknown(unknown)) => {
+                    info!("MockDrone received Unknown Frame: {:?}", unknown);
+                    continue;
+                }
+                Err(err) => {
+                    error!("MockDrone receiving Frame: {}", err);
+                    continue;
+                }
+            };
+
+            // The Ack is sent on 128 + the buffer id and carries only the acknowledged sequence id
+            let ack_reply = |ack_buffer| {
+                let feature = Feature::Unknown {
+                    feature: frame.sequence_id,
+                    data: Vec::new(),
+                };
+
+                (Type::Ack, ack_buffer, Some(feature))
+            };
+
+            let reply = match frame.buffer_id {
+                BufferID::PING => Some((Type::Data, BufferID::PONG, frame.feature.clone())),
+                buffer_id if ack && frame.frame_type == Type::DataWithAck => {
+                    buffer_id.ack_buffer().map(ack_reply)
+                }
+                _ => None,
+            };
+
+            self.received
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(frame);
+
+            if let Some((frame_type, buffer_id, feature)) = reply {
+                if let Err(err) = self.send(frame_type, buffer_id, feature) {
+                    error!("MockDrone replying: {}", err);
+                }
+            }
+        }
+    }
+
+    fn send_ping(&self) -> io::Result<()> {
+        let timestamp = self
+            .controller()
+            .map(|controller| controller.connected_at.elapsed())
+            .unwrap_or_default();
+
+        // `timespec` with the seconds and nanoseconds
+        let mut payload = timestamp.as_secs().to_le_bytes().to_vec();
+        payload.extend_from_slice(&u64::from(timestamp.subsec_nanos()).to_le_bytes());
+        // the PING data is parsed as an unknown feature
+        let feature = Feature::Unknown {
+            feature: payload.remove(0),
+            data: payload,
+        };
+
+        self.send(Type::Data, BufferID::PING, Some(feature))
+    }
+
+    fn send(
+        &self,
+        frame_type: Type,
+        buffer_id: BufferID,
+        feature: Option<Feature>,
+    ) -> io::Result<()> {
+        let controller = match self.controller() {
+            Some(controller) => controller,
+            None => return Err(io::Error::new(ErrorKind::NotConnected, "No Handshake yet")),
+        };
+
+        let frame = Frame::new(frame_type, buffer_id, self.sequence_id(buffer_id), feature);
+
+        let mut raw_frame = [0_u8; 2048];
+        let written = frame
+            .try_into_ctx(&mut raw_frame, LE)
+            .map_err(|err| io::Error::new(ErrorKind::InvalidInput, err))?;
+
+        self.socket
+            .send_to(&raw_frame[..written], controller.d2c_addr)?;
+
+        Ok(())
+    }
+
+    fn sequence_id(&self, buffer_id: BufferID) -> u8 {
+        let mut sequence_id = self.sequence_ids.entry(buffer_id).or_insert(0);
+        let current = *sequence_id;
+        *sequence_id = current.wrapping_add(1);
+
+        current
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        ardrone3::{ArDrone3, FlyingState, Piloting, PilotingState},
+        Config, Drone,
+    };
+
+    fn config(mock: &MockDrone) -> Config {
+        Config::builder(Ipv4Addr::LOCALHOST)
+            .init_port(mock.init_port())
+            // any free port, the Listener is bound before the Handshake
+            .listen_port(0)
+            .handshake_retries(1)
+            .build()
+            .expect("Should be a valid Config")
+    }
+
+    fn wait_for(condition: impl Fn() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(2);
+
+        while !condition() && Instant::now() < deadline {
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    #[test]
+    fn test_connect_to_mock_drone() {
+        let mock = MockDrone::builder()
+            .event(
+                Duration::from_millis(0),
+                Feature::ArDrone3(Some(ArDrone3::PilotingState(
+                    PilotingState::FlyingStateChanged(FlyingState::Hovering),
+                ))),
+            )
+            .start()
+            .expect("Should start the MockDrone");
+
+        let drone = Drone::connect(config(&mock)).expect("Should connect to the MockDrone");
+        assert!(mock.is_connected());
+
+        let take_off = Frame::for_drone(
+            &drone,
+            Type::DataWithAck,
+            BufferID::CDAck,
+            Some(Feature::ArDrone3(Some(ArDrone3::Piloting(
+                Piloting::TakeOff,
+            )))),
+        );
+        drone
+            .send_frame(take_off)
+            .expect("Should send the frame")
+            .wait()
+            .expect("Should be acknowledged");
+
+        assert!(mock.received_frames().iter().any(|frame| frame.feature
+            == Some(Feature::ArDrone3(Some(ArDrone3::Piloting(
+                Piloting::TakeOff
+            ))))));
+
+        wait_for(|| drone.state().flying_state.is_some());
+        assert_eq!(Some(FlyingState::Hovering), drone.state().flying_state);
+
+        drone.disconnect();
+    }
+
+    #[test]
+    fn test_acknowledged_emergency() {
+        let mock = MockDrone::builder()
+            .start()
+            .expect("Should start the MockDrone");
+
+        let drone = Drone::connect(config(&mock)).expect("Should connect to the MockDrone");
+
+        let emergency = Frame::for_drone(
+            &drone,
+            Type::DataWithAck,
+            BufferID::CDEmergency,
+            Some(Feature::ArDrone3(Some(ArDrone3::Piloting(
+                Piloting::Emergency,
+            )))),
+        );
+        drone
+            .send_frame(emergency)
+            .expect("Should send the frame")
+            .wait()
+            .expect("Should be acknowledged");
+
+        drone.disconnect();
+    }
+
+    #[test]
+    fn test_event_is_acknowledged_by_the_controller() {
+        let mock = MockDrone::builder()
+            .ping_interval(None)
+            .start()
+            .expect("Should start the MockDrone");
+
+        let drone = Drone::connect(config(&mock)).expect("Should connect to the MockDrone");
+
+        mock.send_event(Feature::ArDrone3(Some(ArDrone3::PilotingState(
+            PilotingState::FlyingStateChanged(FlyingState::Hovering),
+        ))))
+        .expect("Should send the event");
+
+        // the first event on `BufferID::DCEvent` has sequence id 0
+        let is_event_ack = |frame: &Frame| {
+            frame.frame_type == Type::Ack
+                && frame.buffer_id == BufferID::ACKFromEvent
+                && frame.feature
+                    == Some(Feature::Unknown {
+                        feature: 0,
+                        data: Vec::new(),
+                    })
+        };
+        wait_for(|| mock.received_frames().iter().any(is_event_ack));
+        assert!(mock.received_frames().iter().any(is_event_ack));
+
+        drone.disconnect();
+    }
+
+    #[test]
+    fn test_not_acknowledged_frame() {
+        let mock = MockDrone::builder()
+            .ack(false)
+            .start()
+            .expect("Should start the MockDrone");
+
+        let drone = Drone::connect(config(&mock)).expect("Should connect to the MockDrone");
+
+        let take_off = Frame::for_drone(
+            &drone,
+            Type::DataWithAck,
+            BufferID::CDAck,
+            Some(Feature::ArDrone3(Some(ArDrone3::Piloting(
+                Piloting::TakeOff,
+            )))),
+        );
+        let result = drone
+            .send_frame(take_off)
+            .expect("Should send the frame")
+            .wait();
+
+        assert!(matches!(result, Err(crate::Error::AckTimeout { .. })));
+        // the first send and the re-sends
+        let sent = mock
+            .received_frames()
+            .into_iter()
+            .filter(|frame| frame.buffer_id == BufferID::CDAck)
+            .count();
+        assert_eq!(1 + usize::from(crate::DEFAULT_ACK_RETRIES), sent);
+    }
+
+    #[test]
+    fn test_record_and_replay_session() {
+        let path = std::env::temp_dir().join(format!("arsdk-rs-{}.arsdk", std::process::id()));
+        let hovering = Feature::ArDrone3(Some(ArDrone3::PilotingState(
+            PilotingState::FlyingStateChanged(FlyingState::Hovering),
+        )));
+
+        {
+            let mock = MockDrone::builder()
+                .ping_interval(None)
+                .start()
+                .expect("Should start the MockDrone");
+            let config = Config {
+                recorder: Some(Arc::new(
+                    crate::record::Recorder::create(&path).expect("Should create the capture"),
+                )),
+                ..config(&mock)
+            };
+            let drone = Drone::connect(config).expect("Should connect to the MockDrone");
+
+            mock.send_event(hovering.clone())
+                .expect("Should send the event");
+            wait_for(|| drone.state().flying_state.is_some());
+            drone.disconnect();
+        }
+
+        let records = crate::record::Replayer::open(&path)
+            .expect("Should open the capture")
+            .collect::<io::Result<Vec<_>>>()
+            .expect("Should read the capture");
+        std::fs::remove_file(&path).expect("Should remove the capture");
+
+        assert!(records
+            .iter()
+            .any(|record| record.direction == Direction::Outgoing));
+        assert!(records
+            .iter()
+            .any(|record| record.direction == Direction::Incoming
+                && record.frames().into_iter().any(|frame| match frame {
+                    Ok(FrameType::Known(frame)) => frame.feature == Some(hovering.clone()),
+                    _ => false,
+                })));
+
+        let mock = MockDrone::builder()
+            .ping_interval(None)
+            .start()
+            .expect("Should start the MockDrone");
+        let drone = Drone::connect(config(&mock)).expect("Should connect to the MockDrone");
+
+        mock.replay(records).expect("Should replay the session");
+        wait_for(|| drone.state().flying_state.is_some());
+        assert_eq!(Some(FlyingState::Hovering), drone.state().flying_state);
+    }
+}
@@ -5,22 +5,65 @@ use crate::{
 };
 use log::{error, info};
 use scroll::{Pread, Pwrite, LE};
+use std::{convert::TryFrom, sync::PoisonError, time::Instant};
 
+/// - Keeps the link to the Drone alive and collects its `LinkStats`
 /// - Parses Frames
+/// - Updates the `Drone`'s state and delivers the frames to it's subscriptions, only once per sequence id
 /// - Sends PING response to cmd Sender
+/// - Matches the Acks of the `Type::DataWithAck` frames that we've sent
+/// - Acknowledges the `Type::DataWithAck` frames of the Drone, e.g. its events
 /// - Logs unknown frames
 pub(crate) fn handle_bytes(drone: &Drone, raw_frames: &[u8]) {
+    drone.inner.link.received();
+    // stops waiting for the `Policy::Block` subscribers once this link is closed
+    let link = drone.downgrade_link();
+
     let frames = parse_message_frames(&raw_frames);
 
+    drone
+        .inner
+        .link_stats
+        .received(Instant::now(), raw_frames.len(), frames.len());
+
     for result in frames.iter() {
         match result {
-            Ok(FrameType::Known(frame)) => info!("Frame: {:?}", frame),
+            Ok(FrameType::Known(frame)) => {
+                info!("Frame: {:?}", frame);
+
+                drone
+                    .inner
+                    .link_stats
+                    .sequence_id_received(frame.buffer_id, frame.sequence_id);
+
+                // re-sent by the Drone because our Ack got lost, it's still acknowledged below
+                if is_duplicate(drone, frame) {
+                    info!("Duplicate Frame: {:?}", frame);
+                    continue;
+                }
+
+                if let Some(feature) = frame.feature.as_ref() {
+                    drone
+                        .inner
+                        .state
+                        .write()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .update(feature);
+                }
+            }
             Ok(FrameType::Unknown(unknown)) => {
                 info!("Unknown Frame: {:?}", unknown);
                 info!("Bytes: {}", print_buf(raw_frames));
             }
             Err(err) => error!("Receiving Frame: {:?}", err),
         }
+
+        if let Ok(frame_type) = result {
+            drone
+                .inner
+                .subscribers
+                .publish(frame_type, || link.upgrade().is_some());
+        }
     }
 
     for result in frames.iter() {
@@ -31,26 +74,47 @@ pub(crate) fn handle_bytes(drone: &Drone, raw_frames: &[u8]) {
                     error!("Sending Frame to Commander: {}", err)
                 }
             }
-            // Data that needs Ack coming from the Drone
-            Ok(FrameType::Known(frame)) if frame.buffer_id == BufferID::DCNavdata => {
-                let ack_type = Type::Ack;
-                let ack_buffer = BufferID::CDAck;
-                let ack_sequence_id = frame.sequence_id;
-                let feature = frame.feature.as_ref().and_then(|feature| {
-                    match feature {
-                        // we return an empty ArDrone3 as the drone does for our Frames
-                        Feature::ArDrone3(_) => Some(Feature::ArDrone3(None)),
-                        //
-                        _ => None,
+            // PONG for our PING
+            Ok(FrameType::Known(frame)) if frame.buffer_id == BufferID::PONG => {
+                if let Some(Feature::Unknown { feature, data }) = frame.feature.as_ref() {
+                    let payload = [&[*feature], data.as_slice()].concat();
+
+                    drone
+                        .inner
+                        .link_stats
+                        .pong_received(Instant::now(), &payload);
+                }
+            }
+            // Ack from the Drone for a `Type::DataWithAck` frame that we've sent
+            Ok(FrameType::Known(frame)) if frame.buffer_id.is_ack() => {
+                // The Ack is sent on 128 + the acknowledged buffer id
+                let ack_buffer: u8 = frame.buffer_id.into();
+                let acked_buffer = BufferID::try_from(ack_buffer - 128);
+
+                // The only data of the Ack is the acknowledged sequence id, read as a raw byte
+                match (acked_buffer, frame.feature.as_ref()) {
+                    (Ok(acked_buffer), Some(Feature::Unknown { feature, .. })) => {
+                        drone.ack_received(acked_buffer, *feature)
                     }
-                });
-
-                let ack_frame = Frame {
-                    frame_type: ack_type,
-                    buffer_id: ack_buffer,
-                    feature,
-                    sequence_id: ack_sequence_id,
+                    _ => error!("Ack without a known buffer or sequence id: {:?}", frame),
+                }
+            }
+            // Data that needs Ack coming from the Drone, e.g. the events on `BufferID::DCEvent`
+            Ok(FrameType::Known(frame)) if frame.frame_type == Type::DataWithAck => {
+                // The Ack is sent on 128 + the buffer id and carries only the acknowledged sequence id
+                let ack_buffer = match frame.buffer_id.ack_buffer() {
+                    Some(ack_buffer) => ack_buffer,
+                    None => {
+                        error!("No Ack buffer for: {:?}", frame);
+                        continue;
+                    }
+                };
+                let feature = Feature::Unknown {
+                    feature: frame.sequence_id,
+                    data: Vec::new(),
                 };
+
+                let ack_frame = Frame::for_drone(drone, Type::Ack, ack_buffer, Some(feature));
                 if let Err(err) = drone.send_frame(ack_frame) {
                     error!("Sending Frame to Commander: {}", err)
                 }
@@ -61,8 +125,17 @@ pub(crate) fn handle_bytes(drone: &Drone, raw_frames: &[u8]) {
     }
 }
 
-/// Parses the Frames from a buffer
-pub(crate) fn parse_message_frames(buf: &[u8]) -> Vec<Result<FrameType, Error>> {
+fn is_duplicate(drone: &Drone, frame: &Frame) -> bool {
+    // PINGs and PONGs are never re-sent
+    ![BufferID::PING, BufferID::PONG].contains(&frame.buffer_id)
+        && drone
+            .inner
+            .received_sequence_ids
+            .is_duplicate(frame.buffer_id, frame.sequence_id)
+}
+
+/// Parses the Frames from a buffer, e.g. a received datagram
+pub fn parse_message_frames(buf: &[u8]) -> Vec<Result<FrameType, Error>> {
     let mut offset = 0;
     // TODO: Check how many frames can we receive at once
     // reasonable given that we receive at most (MAYBE?!) 2 frames
@@ -92,6 +165,34 @@ pub(crate) fn read_unknown(src: &[u8], offset: &mut usize) -> Result<Vec<u8>, cr
     Ok(feature_data[..actual_written].to_vec())
 }
 
+/// Helper function to read a null terminated string argument
+pub(crate) fn read_string(src: &[u8], offset: &mut usize) -> Result<String, crate::frame::Error> {
+    let rest = src.get(*offset..).unwrap_or_default();
+    let len = rest
+        .iter()
+        .position(|byte| *byte == 0)
+        .ok_or(scroll::Error::BadInput {
+            size: rest.len(),
+            msg: "String without the null terminator",
+        })?;
+
+    *offset += len + 1;
+
+    Ok(String::from_utf8_lossy(&rest[..len]).into_owned())
+}
+
+/// Helper function to write a string argument with its null terminator
+pub(crate) fn write_string(
+    this: &mut [u8],
+    offset: &mut usize,
+    string: &str,
+) -> Result<(), crate::frame::Error> {
+    this.gwrite_with(string.as_bytes(), offset, ())?;
+    this.gwrite_with(0_u8, offset, LE)?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod parse_message_frames {
     use super::*;
@@ -152,6 +253,7 @@ mod parse_message_frames {
     }
 
     #[test]
+    /// The acknowledged sequence id 0 is at the position of the Common Feature
     fn test_feature_common_none() {
         let buf: [u8; 8] = [1, 139, 0, 8, 0, 0, 0, 0];
 
@@ -159,7 +261,10 @@ mod parse_message_frames {
             frame_type: Type::Ack,
             buffer_id: BufferID::ACKFromSendWithAck,
             sequence_id: 0,
-            feature: Some(Feature::Common(None)),
+            feature: Some(Feature::Unknown {
+                feature: 0,
+                data: vec![],
+            }),
         };
 
         let actual = parse_message_frames(&buf);
@@ -0,0 +1,817 @@
+//! Importing the ARNetworkAL datagrams from tcpdump captures and exporting recorded sessions.
+//!
+//! The `PcapReader` reads pcap and pcapng files and extracts the UDP payloads sent to the
+//! `d2c_port` (`Direction::Incoming`) and to the `c2d_port` (`Direction::Outgoing`) as `Record`s,
+//! which are decoded with `Record::frames()`.
+//!
+//! Supported link types are Ethernet, raw IP, BSD loopback and Linux cooked captures (SLL and SLL2).
+//! Fragmented IP packets are skipped.
+//!
+//! ```ignore
+//! use arsdk_rs::{
+//!     pcap::{PcapReader, PcapWriter, Ports, DEFAULT_C2D_PORT},
+//!     record::Replayer,
+//!     LISTEN_PORT,
+//! };
+//!
+//! // tcpdump -i wlan0 -w flight.pcapng udp
+//! for record in PcapReader::open("flight.pcapng", Ports::default())? {
+//!     let record = record?;
+//!     println!("{:?}: {:?}", record.direction, record.frames());
+//! }
+//!
+//! // open a recorded session in Wireshark
+//! let mut writer = PcapWriter::create(
+//!     "flight.pcap",
+//!     ([192, 168, 42, 1], DEFAULT_C2D_PORT).into(),
+//!     ([192, 168, 42, 2], LISTEN_PORT).into(),
+//! )?;
+//! for record in Replayer::open("flight.arsdk")? {
+//!     writer.write(&record?)?;
+//! }
+//! ```
+use crate::{
+    record::{invalid_data, Direction, Record},
+    LISTEN_PORT,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use scroll::{Endian, Pread, Pwrite, BE, LE};
+use std::{
+    convert::TryFrom,
+    fs::File,
+    io::{self, BufReader, BufWriter, ErrorKind, Read, Write},
+    net::{IpAddr, SocketAddr},
+    path::Path,
+};
+
+/// The `c2d_port` that the Parrot Drones send in the Handshake
+pub const DEFAULT_C2D_PORT: u16 = 54321;
+
+const PCAP_MAGIC_MICROS: u32 = 0xa1b2_c3d4;
+const PCAP_MAGIC_NANOS: u32 = 0xa1b2_3c4d;
+const PCAPNG_SECTION_HEADER: u32 = 0x0a0d_0d0a;
+const PCAPNG_BYTE_ORDER_MAGIC: u32 = 0x1a2b_3c4d;
+const PCAPNG_INTERFACE_DESCRIPTION: u32 = 1;
+const PCAPNG_SIMPLE_PACKET: u32 = 3;
+const PCAPNG_ENHANCED_PACKET: u32 = 6;
+/// The `if_tsresol` option of the Interface Description Block
+const PCAPNG_OPTION_TSRESOL: u16 = 9;
+/// Protects from allocating a huge buffer for a corrupted length
+const MAX_BLOCK_LEN: usize = 16 * 1024 * 1024;
+
+const LINKTYPE_NULL: u32 = 0;
+const LINKTYPE_ETHERNET: u32 = 1;
+const LINKTYPE_RAW: u32 = 101;
+const LINKTYPE_LINUX_SLL: u32 = 113;
+const LINKTYPE_IPV4: u32 = 228;
+const LINKTYPE_IPV6: u32 = 229;
+const LINKTYPE_LINUX_SLL2: u32 = 276;
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86dd;
+const ETHERTYPE_VLAN: u16 = 0x8100;
+const IP_PROTOCOL_UDP: u8 = 17;
+
+const IPV4_HEADER_LEN: usize = 20;
+const IPV6_HEADER_LEN: usize = 40;
+const UDP_HEADER_LEN: usize = 8;
+
+/// The UDP ports of the ARNetworkAL traffic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ports {
+    /// The port at which the controller listens, `Config::listen_port`
+    pub d2c_port: u16,
+    /// The port at which the Drone listens, from the Handshake response
+    pub c2d_port: u16,
+}
+
+impl Default for Ports {
+    fn default() -> Self {
+        Self {
+            d2c_port: LISTEN_PORT,
+            c2d_port: DEFAULT_C2D_PORT,
+        }
+    }
+}
+
+impl Ports {
+    fn direction(&self, destination_port: u16) -> Option<Direction> {
+        if destination_port == self.d2c_port {
+            Some(Direction::Incoming)
+        } else if destination_port == self.c2d_port {
+            Some(Direction::Outgoing)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Interface {
+    link_type: u32,
+    /// Units of the timestamps
+    units_per_second: u64,
+}
+
+#[derive(Debug)]
+enum Format {
+    Pcap {
+        endian: Endian,
+        units_per_second: u64,
+        link_type: u32,
+    },
+    PcapNg {
+        endian: Endian,
+        interfaces: Vec<Interface>,
+    },
+}
+
+/// A captured link layer packet
+struct Packet {
+    timestamp: DateTime<Utc>,
+    link_type: u32,
+    data: Vec<u8>,
+}
+
+/// Reads the ARNetworkAL datagrams of a pcap or pcapng capture
+#[derive(Debug)]
+pub struct PcapReader<R> {
+    reader: R,
+    ports: Ports,
+    format: Format,
+    /// The Simple Packet Blocks of pcapng don't have a timestamp
+    last_timestamp: DateTime<Utc>,
+}
+
+impl PcapReader<BufReader<File>> {
+    pub fn open(path: impl AsRef<Path>, ports: Ports) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?), ports)
+    }
+}
+
+impl<R: Read> PcapReader<R> {
+    /// Reads the file header and detects if it's a pcap or a pcapng capture
+    pub fn new(mut reader: R, ports: Ports) -> io::Result<Self> {
+        let mut magic = [0_u8; 4];
+        reader.read_exact(&mut magic)?;
+
+        let format = match (
+            magic.pread_with::<u32>(0, LE),
+            magic.pread_with::<u32>(0, BE),
+        ) {
+            (Ok(PCAPNG_SECTION_HEADER), _) => Format::PcapNg {
+                endian: read_section_header(&mut reader)?,
+                interfaces: Vec::new(),
+            },
+            (Ok(magic), _) if magic == PCAP_MAGIC_MICROS || magic == PCAP_MAGIC_NANOS => {
+                read_pcap_header(&mut reader, LE, magic)?
+            }
+            (_, Ok(magic)) if magic == PCAP_MAGIC_MICROS || magic == PCAP_MAGIC_NANOS => {
+                read_pcap_header(&mut reader, BE, magic)?
+            }
+            _ => return Err(invalid_data("Not a pcap or pcapng capture")),
+        };
+
+        Ok(Self {
+            reader,
+            ports,
+            format,
+            last_timestamp: Utc.timestamp(0, 0),
+        })
+    }
+
+    fn read_record(&mut self) -> io::Result<Option<Record>> {
+        while let Some(packet) = self.read_packet()? {
+            let datagram = match udp_datagram(packet.link_type, &packet.data) {
+                Some(datagram) => datagram,
+                None => continue,
+            };
+
+            if let Some(direction) = self.ports.direction(datagram.destination_port) {
+                return Ok(Some(Record {
+                    direction,
+                    timestamp: packet.timestamp,
+                    data: datagram.payload.to_vec(),
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn read_packet(&mut self) -> io::Result<Option<Packet>> {
+        match self.format {
+            Format::Pcap {
+                endian,
+                units_per_second,
+                link_type,
+            } => {
+                let mut header = [0_u8; 16];
+                if !read_next(&mut self.reader, &mut header)? {
+                    return Ok(None);
+                }
+
+                let seconds = header.pread_with::<u32>(0, endian).map_err(invalid_data)?;
+                let fraction = header.pread_with::<u32>(4, endian).map_err(invalid_data)?;
+                let captured_len = header.pread_with::<u32>(8, endian).map_err(invalid_data)?;
+
+                let mut data = vec![0_u8; checked_len(captured_len)?];
+                self.reader.read_exact(&mut data)?;
+
+                Ok(Some(Packet {
+                    timestamp: timestamp(
+                        u64::from(seconds) * units_per_second + u64::from(fraction),
+                        units_per_second,
+                    )?,
+                    link_type,
+                    data,
+                }))
+            }
+            Format::PcapNg { .. } => self.read_pcapng_packet(),
+        }
+    }
+
+    /// Reads the blocks until the next packet, skipping the blocks we don't need
+    fn read_pcapng_packet(&mut self) -> io::Result<Option<Packet>> {
+        loop {
+            let mut block_type = [0_u8; 4];
+            if !read_next(&mut self.reader, &mut block_type)? {
+                return Ok(None);
+            }
+
+            // the magic is the same in both byte orders
+            if block_type == PCAPNG_SECTION_HEADER.to_le_bytes() {
+                self.format = Format::PcapNg {
+                    endian: read_section_header(&mut self.reader)?,
+                    interfaces: Vec::new(),
+                };
+                continue;
+            }
+
+            let (endian, interfaces) = match &mut self.format {
+                Format::PcapNg { endian, interfaces } => (*endian, interfaces),
+                Format::Pcap { .. } => unreachable!("Only pcapng has blocks"),
+            };
+
+            let block_type = block_type
+                .pread_with::<u32>(0, endian)
+                .map_err(invalid_data)?;
+            let body = read_block_body(&mut self.reader, endian)?;
+
+            match block_type {
+                PCAPNG_INTERFACE_DESCRIPTION => interfaces.push(read_interface(&body, endian)?),
+                PCAPNG_ENHANCED_PACKET => {
+                    let interface_id = body.pread_with::<u32>(0, endian).map_err(invalid_data)?;
+                    let high = body.pread_with::<u32>(4, endian).map_err(invalid_data)?;
+                    let low = body.pread_with::<u32>(8, endian).map_err(invalid_data)?;
+                    let captured_len = body.pread_with::<u32>(12, endian).map_err(invalid_data)?;
+
+                    let interface = interfaces
+                        .get(interface_id as usize)
+                        .ok_or_else(|| invalid_data("Packet of an unknown interface"))?;
+                    let data = body
+                        .get(20..20 + checked_len(captured_len)?)
+                        .ok_or_else(|| invalid_data("Truncated Enhanced Packet Block"))?;
+
+                    self.last_timestamp = timestamp(
+                        u64::from(high) << 32 | u64::from(low),
+                        interface.units_per_second,
+                    )?;
+
+                    return Ok(Some(Packet {
+                        timestamp: self.last_timestamp,
+                        link_type: interface.link_type,
+                        data: data.to_vec(),
+                    }));
+                }
+                PCAPNG_SIMPLE_PACKET => {
+                    let interface = interfaces
+                        .first()
+                        .ok_or_else(|| invalid_data("Packet of an unknown interface"))?;
+                    let original_len = body.pread_with::<u32>(0, endian).map_err(invalid_data)?;
+                    // the rest of the block is padding, if the packet wasn't truncated
+                    let captured_len = (body.len() - 4).min(checked_len(original_len)?);
+
+                    return Ok(Some(Packet {
+                        timestamp: self.last_timestamp,
+                        link_type: interface.link_type,
+                        data: body[4..4 + captured_len].to_vec(),
+                    }));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for PcapReader<R> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+/// Reads the rest of the classic pcap file header after the `magic`
+fn read_pcap_header(reader: &mut impl Read, endian: Endian, magic: u32) -> io::Result<Format> {
+    let mut header = [0_u8; 20];
+    reader.read_exact(&mut header)?;
+
+    Ok(Format::Pcap {
+        endian,
+        units_per_second: if magic == PCAP_MAGIC_NANOS {
+            1_000_000_000
+        } else {
+            1_000_000
+        },
+        link_type: header.pread_with::<u32>(16, endian).map_err(invalid_data)?,
+    })
+}
+
+/// Reads the rest of the Section Header Block after the block type and returns its byte order
+fn read_section_header(reader: &mut impl Read) -> io::Result<Endian> {
+    let mut header = [0_u8; 8];
+    reader.read_exact(&mut header)?;
+
+    let endian = match header.pread_with::<u32>(4, LE).map_err(invalid_data)? {
+        PCAPNG_BYTE_ORDER_MAGIC => LE,
+        magic if magic.swap_bytes() == PCAPNG_BYTE_ORDER_MAGIC => BE,
+        _ => return Err(invalid_data("Unknown pcapng byte order")),
+    };
+
+    let block_len = header.pread_with::<u32>(0, endian).map_err(invalid_data)?;
+    // the type, length and byte order magic are already read
+    let rest = checked_block_len(block_len)?
+        .checked_sub(12)
+        .ok_or_else(|| invalid_data("Truncated Section Header Block"))?;
+    io::copy(&mut reader.take(rest as u64), &mut io::sink())?;
+
+    Ok(endian)
+}
+
+/// Reads the block after its type, without the trailing block length
+fn read_block_body(reader: &mut impl Read, endian: Endian) -> io::Result<Vec<u8>> {
+    let mut block_len = [0_u8; 4];
+    reader.read_exact(&mut block_len)?;
+    let block_len = block_len
+        .pread_with::<u32>(0, endian)
+        .map_err(invalid_data)?;
+
+    let mut body = vec![0_u8; checked_block_len(block_len)?.saturating_sub(8)];
+    reader.read_exact(&mut body)?;
+    body.truncate(body.len().saturating_sub(4));
+
+    Ok(body)
+}
+
+fn read_interface(body: &[u8], endian: Endian) -> io::Result<Interface> {
+    let link_type = body.pread_with::<u16>(0, endian).map_err(invalid_data)?;
+    let mut units_per_second = 1_000_000;
+
+    // the options start after the link type, reserved and snap length
+    let mut offset = 8;
+    while let (Ok(code), Ok(len)) = (
+        body.pread_with::<u16>(offset, endian),
+        body.pread_with::<u16>(offset + 2, endian),
+    ) {
+        let value = body.get(offset + 4..offset + 4 + len as usize);
+
+        match (code, value) {
+            // end of options
+            (0, _) => break,
+            (PCAPNG_OPTION_TSRESOL, Some(&[resolution])) => {
+                let exponent = u32::from(resolution & 0x7f);
+                let base: u64 = if resolution & 0x80 == 0 { 10 } else { 2 };
+
+                units_per_second = base
+                    .checked_pow(exponent)
+                    .ok_or_else(|| invalid_data("Unsupported timestamp resolution"))?;
+            }
+            _ => {}
+        }
+
+        // options are padded to 32 bits
+        offset += 4 + (len as usize + 3) / 4 * 4;
+    }
+
+    Ok(Interface {
+        link_type: link_type.into(),
+        units_per_second,
+    })
+}
+
+/// Reads the whole `buf`, returns `false` if the capture ended before it
+fn read_next(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<bool> {
+    match reader.read(&mut buf[..1])? {
+        0 => Ok(false),
+        _ => reader.read_exact(&mut buf[1..]).map(|_| true),
+    }
+}
+
+fn checked_len(len: u32) -> io::Result<usize> {
+    match len as usize {
+        len if len <= MAX_BLOCK_LEN => Ok(len),
+        _ => Err(invalid_data("Packet is too long")),
+    }
+}
+
+fn checked_block_len(len: u32) -> io::Result<usize> {
+    match checked_len(len)? {
+        len if len >= 12 && len % 4 == 0 => Ok(len),
+        _ => Err(invalid_data("Invalid pcapng block length")),
+    }
+}
+
+fn timestamp(units: u64, units_per_second: u64) -> io::Result<DateTime<Utc>> {
+    let seconds = i64::try_from(units / units_per_second).map_err(invalid_data)?;
+    let nanos = u128::from(units % units_per_second) * 1_000_000_000 / u128::from(units_per_second);
+
+    Utc.timestamp_opt(seconds, nanos as u32)
+        .single()
+        .ok_or_else(|| invalid_data("Invalid timestamp"))
+}
+
+struct Datagram<'a> {
+    destination_port: u16,
+    payload: &'a [u8],
+}
+
+/// Extracts the UDP datagram of the packet, `None` for anything else
+fn udp_datagram(link_type: u32, packet: &[u8]) -> Option<Datagram<'_>> {
+    let ip_packet = match link_type {
+        LINKTYPE_ETHERNET => {
+            let mut ether_type = packet.pread_with::<u16>(12, BE).ok()?;
+            let mut offset = 14;
+            if ether_type == ETHERTYPE_VLAN {
+                ether_type = packet.pread_with::<u16>(16, BE).ok()?;
+                offset = 18;
+            }
+
+            match ether_type {
+                ETHERTYPE_IPV4 | ETHERTYPE_IPV6 => packet.get(offset..)?,
+                _ => return None,
+            }
+        }
+        // the address family is in the byte order of the capturing host
+        LINKTYPE_NULL => packet.get(4..)?,
+        LINKTYPE_LINUX_SLL => packet.get(16..)?,
+        LINKTYPE_LINUX_SLL2 => packet.get(20..)?,
+        LINKTYPE_RAW | LINKTYPE_IPV4 | LINKTYPE_IPV6 => packet,
+        _ => return None,
+    };
+
+    let udp_packet = match ip_packet.first()? >> 4 {
+        4 => {
+            let header_len = usize::from(ip_packet[0] & 0x0f) * 4;
+            let total_len = ip_packet.pread_with::<u16>(2, BE).ok()?;
+            let fragment = ip_packet.pread_with::<u16>(6, BE).ok()?;
+            // more fragments or a fragment offset
+            if fragment & 0x3fff != 0 || *ip_packet.get(9)? != IP_PROTOCOL_UDP {
+                return None;
+            }
+
+            // without the Ethernet padding
+            ip_packet.get(header_len..usize::from(total_len).min(ip_packet.len()))?
+        }
+        // extension headers are not supported
+        6 if *ip_packet.get(6)? == IP_PROTOCOL_UDP => ip_packet.get(IPV6_HEADER_LEN..)?,
+        _ => return None,
+    };
+
+    let destination_port = udp_packet.pread_with::<u16>(2, BE).ok()?;
+    let udp_len = udp_packet.pread_with::<u16>(4, BE).ok()?;
+
+    Some(Datagram {
+        destination_port,
+        payload: udp_packet.get(UDP_HEADER_LEN..usize::from(udp_len).min(udp_packet.len()))?,
+    })
+}
+
+/// Writes `Record`s as a pcap capture with raw IP packets, e.g. for opening them in Wireshark
+#[derive(Debug)]
+pub struct PcapWriter<W: Write> {
+    writer: W,
+    /// The Drone address and its `c2d_port`
+    drone: SocketAddr,
+    /// The controller address and its `d2c_port`
+    controller: SocketAddr,
+}
+
+impl PcapWriter<BufWriter<File>> {
+    /// Creates (or truncates) the capture file
+    pub fn create(
+        path: impl AsRef<Path>,
+        drone: SocketAddr,
+        controller: SocketAddr,
+    ) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?), drone, controller)
+    }
+}
+
+impl<W: Write> PcapWriter<W> {
+    /// Writes the pcap file header.
+    ///
+    /// The `Direction::Incoming` records are sent from the `drone` to the `controller`
+    /// and the `Direction::Outgoing` ones the other way around.
+    /// Both addresses should be either IPv4 or IPv6.
+    pub fn new(mut writer: W, drone: SocketAddr, controller: SocketAddr) -> io::Result<Self> {
+        if drone.is_ipv4() != controller.is_ipv4() {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "Drone and controller addresses should be of the same IP version",
+            ));
+        }
+
+        let mut header = [0_u8; 24];
+        let mut offset = 0;
+        header
+            .gwrite_with::<u32>(PCAP_MAGIC_MICROS, &mut offset, LE)
+            // version 2.4
+            .and_then(|_| header.gwrite_with::<u16>(2, &mut offset, LE))
+            .and_then(|_| header.gwrite_with::<u16>(4, &mut offset, LE))
+            // time zone and accuracy of the timestamps
+            .and_then(|_| header.gwrite_with::<u64>(0, &mut offset, LE))
+            .and_then(|_| header.gwrite_with::<u32>(u16::MAX.into(), &mut offset, LE))
+            .and_then(|_| header.gwrite_with::<u32>(LINKTYPE_RAW, &mut offset, LE))
+            .map_err(|err| io::Error::new(ErrorKind::InvalidInput, err))?;
+        writer.write_all(&header)?;
+
+        Ok(Self {
+            writer,
+            drone,
+            controller,
+        })
+    }
+
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        let (source, destination) = match record.direction {
+            Direction::Incoming => (self.drone, self.controller),
+            Direction::Outgoing => (self.controller, self.drone),
+        };
+        let packet = ip_packet(source, destination, &record.data)?;
+
+        let seconds = u32::try_from(record.timestamp.timestamp())
+            .map_err(|_| io::Error::new(ErrorKind::InvalidInput, "Timestamp out of range"))?;
+        let mut header = [0_u8; 16];
+        let mut offset = 0;
+        header
+            .gwrite_with::<u32>(seconds, &mut offset, LE)
+            .and_then(|_| {
+                header.gwrite_with(record.timestamp.timestamp_subsec_micros(), &mut offset, LE)
+            })
+            // captured and original length
+            .and_then(|_| header.gwrite_with(packet.len() as u32, &mut offset, LE))
+            .and_then(|_| header.gwrite_with(packet.len() as u32, &mut offset, LE))
+            .map_err(|err| io::Error::new(ErrorKind::InvalidInput, err))?;
+
+        self.writer.write_all(&header)?;
+        self.writer.write_all(&packet)
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Wraps the payload in UDP and IP headers
+fn ip_packet(source: SocketAddr, destination: SocketAddr, payload: &[u8]) -> io::Result<Vec<u8>> {
+    let too_long = || io::Error::new(ErrorKind::InvalidInput, "Datagram is too long");
+    let udp_len = u16::try_from(UDP_HEADER_LEN + payload.len()).map_err(|_| too_long())?;
+
+    let mut udp = vec![0_u8; UDP_HEADER_LEN];
+    udp.pwrite_with(source.port(), 0, BE)
+        .map_err(invalid_data)?;
+    udp.pwrite_with(destination.port(), 2, BE)
+        .map_err(invalid_data)?;
+    udp.pwrite_with(udp_len, 4, BE).map_err(invalid_data)?;
+    udp.extend_from_slice(payload);
+
+    let mut packet = match (source.ip(), destination.ip()) {
+        (IpAddr::V4(source), IpAddr::V4(destination)) => {
+            let total_len = u16::try_from(IPV4_HEADER_LEN + udp.len()).map_err(|_| too_long())?;
+
+            let mut header = vec![0_u8; IPV4_HEADER_LEN];
+            // version 4 and 5 * 32 bits of header
+            header[0] = 0x45;
+            header.pwrite_with(total_len, 2, BE).map_err(invalid_data)?;
+            // don't fragment
+            header[6] = 0x40;
+            // time to live
+            header[8] = 64;
+            header[9] = IP_PROTOCOL_UDP;
+            header[12..16].copy_from_slice(&source.octets());
+            header[16..20].copy_from_slice(&destination.octets());
+            header
+                .pwrite_with(checksum(&header), 10, BE)
+                .map_err(invalid_data)?;
+
+            // the UDP checksum is optional for IPv4
+            header
+        }
+        (IpAddr::V6(source), IpAddr::V6(destination)) => {
+            let mut header = vec![0_u8; IPV6_HEADER_LEN];
+            header[0] = 0x60;
+            header.pwrite_with(udp_len, 4, BE).map_err(invalid_data)?;
+            header[6] = IP_PROTOCOL_UDP;
+            // hop limit
+            header[7] = 64;
+            header[8..24].copy_from_slice(&source.octets());
+            header[24..40].copy_from_slice(&destination.octets());
+
+            let mut pseudo_header = header[8..40].to_vec();
+            pseudo_header.extend_from_slice(&u32::from(udp_len).to_be_bytes());
+            pseudo_header.extend_from_slice(&[0, 0, 0, IP_PROTOCOL_UDP]);
+            pseudo_header.extend_from_slice(&udp);
+            // zero means no checksum, which is not allowed for IPv6
+            let udp_checksum = match checksum(&pseudo_header) {
+                0 => 0xffff,
+                udp_checksum => udp_checksum,
+            };
+            udp.pwrite_with(udp_checksum, 6, BE).map_err(invalid_data)?;
+
+            header
+        }
+        _ => {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "Mixed IPv4 and IPv6 addresses",
+            ))
+        }
+    };
+
+    packet.extend_from_slice(&udp);
+    Ok(packet)
+}
+
+/// The Internet checksum (RFC 1071)
+fn checksum(bytes: &[u8]) -> u16 {
+    let mut sum = bytes
+        .chunks(2)
+        .map(|word| match word {
+            [high, low] => u32::from(u16::from_be_bytes([*high, *low])),
+            [high] => u32::from(*high) << 8,
+            _ => unreachable!("Chunks of 2"),
+        })
+        .sum::<u32>();
+
+    while sum > 0xffff {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+
+    !(sum as u16)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frame::{BufferID, Frame, FrameType, Type};
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                direction: Direction::Outgoing,
+                timestamp: Utc.timestamp(1_590_000_000, 123_456_000),
+                data: vec![4, 11, 1, 11, 0, 0, 0, 1, 0, 1, 0],
+            },
+            Record {
+                direction: Direction::Incoming,
+                timestamp: Utc.timestamp(1_590_000_000, 130_000_000),
+                data: vec![1, 139, 0, 8, 0, 0, 0, 0],
+            },
+        ]
+    }
+
+    fn round_trip(drone: SocketAddr, controller: SocketAddr) -> Vec<Record> {
+        let mut writer =
+            PcapWriter::new(Vec::new(), drone, controller).expect("Should write the header");
+        for record in records() {
+            writer.write(&record).expect("Should write the record");
+        }
+        let capture = writer.into_inner();
+
+        let ports = Ports {
+            d2c_port: controller.port(),
+            c2d_port: drone.port(),
+        };
+        PcapReader::new(capture.as_slice(), ports)
+            .expect("Should be a pcap capture")
+            .collect::<io::Result<Vec<_>>>()
+            .expect("Should read the records")
+    }
+
+    #[test]
+    fn test_pcap_round_trip() {
+        let read = round_trip(
+            ([192, 168, 42, 1], DEFAULT_C2D_PORT).into(),
+            ([192, 168, 42, 2], LISTEN_PORT).into(),
+        );
+        assert_eq!(records(), read);
+
+        match read[1].frames().pop() {
+            Some(Ok(FrameType::Known(Frame {
+                frame_type: Type::Ack,
+                buffer_id: BufferID::ACKFromSendWithAck,
+                ..
+            }))) => {}
+            frame => panic!("Unexpected frame: {:?}", frame),
+        }
+
+        let ipv6 = round_trip(
+            "[fe80::1]:54321".parse().unwrap(),
+            "[fe80::2]:43210".parse().unwrap(),
+        );
+        assert_eq!(records(), ipv6);
+    }
+
+    #[test]
+    fn test_checksum() {
+        // the example IPv4 header with the checksum set
+        let header = [
+            0x45, 0x00, 0x00, 0x73, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11, 0xb8, 0x61, 0xc0, 0xa8,
+            0x00, 0x01, 0xc0, 0xa8, 0x00, 0xc7,
+        ];
+        assert_eq!(0, checksum(&header));
+    }
+
+    /// Appends a pcapng block with the little endian block type and lengths
+    fn block(capture: &mut Vec<u8>, block_type: u32, body: &[u8]) {
+        let padded_len = (body.len() + 3) / 4 * 4;
+        let block_len = (12 + padded_len) as u32;
+
+        capture.extend_from_slice(&block_type.to_le_bytes());
+        capture.extend_from_slice(&block_len.to_le_bytes());
+        capture.extend_from_slice(body);
+        capture.resize(capture.len() + padded_len - body.len(), 0);
+        capture.extend_from_slice(&block_len.to_le_bytes());
+    }
+
+    fn ethernet_udp(destination_port: u16, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0_u8; 12];
+        frame.extend_from_slice(&ETHERTYPE_IPV4.to_be_bytes());
+        frame.extend(
+            ip_packet(
+                ([192, 168, 42, 1], 5000).into(),
+                ([192, 168, 42, 2], destination_port).into(),
+                payload,
+            )
+            .expect("Should fit"),
+        );
+        // Ethernet padding
+        frame.extend_from_slice(&[0; 4]);
+
+        frame
+    }
+
+    #[test]
+    fn test_pcapng() {
+        let mut capture = Vec::new();
+
+        let mut section = PCAPNG_BYTE_ORDER_MAGIC.to_le_bytes().to_vec();
+        // version 1.0 and unknown section length
+        section.extend_from_slice(&[1, 0, 0, 0]);
+        section.extend_from_slice(&(-1_i64).to_le_bytes());
+        block(&mut capture, PCAPNG_SECTION_HEADER, &section);
+
+        let mut interface = (LINKTYPE_ETHERNET as u16).to_le_bytes().to_vec();
+        interface.extend_from_slice(&[0, 0, 0xff, 0xff, 0, 0]);
+        // nanosecond timestamps
+        interface.extend_from_slice(&[9, 0, 1, 0, 9, 0, 0, 0]);
+        interface.extend_from_slice(&[0, 0, 0, 0]);
+        block(&mut capture, PCAPNG_INTERFACE_DESCRIPTION, &interface);
+
+        let navdata = [2, 127, 20, 11, 0, 0, 0];
+        let units: u64 = 1_590_000_000_500_000_000;
+        for destination_port in &[5353, LISTEN_PORT] {
+            let packet = ethernet_udp(*destination_port, &navdata);
+
+            let mut enhanced = 0_u32.to_le_bytes().to_vec();
+            enhanced.extend_from_slice(&((units >> 32) as u32).to_le_bytes());
+            enhanced.extend_from_slice(&(units as u32).to_le_bytes());
+            enhanced.extend_from_slice(&(packet.len() as u32).to_le_bytes());
+            enhanced.extend_from_slice(&(packet.len() as u32).to_le_bytes());
+            enhanced.extend_from_slice(&packet);
+            block(&mut capture, PCAPNG_ENHANCED_PACKET, &enhanced);
+        }
+
+        let read = PcapReader::new(capture.as_slice(), Ports::default())
+            .expect("Should be a pcapng capture")
+            .collect::<io::Result<Vec<_>>>()
+            .expect("Should read the records");
+
+        let expected = Record {
+            direction: Direction::Incoming,
+            timestamp: Utc.timestamp(1_590_000_000, 500_000_000),
+            data: navdata.to_vec(),
+        };
+        assert_eq!(vec![expected], read);
+    }
+
+    #[test]
+    fn test_not_a_capture() {
+        assert!(PcapReader::new(&b"not a capture"[..], Ports::default()).is_err());
+    }
+}
@@ -0,0 +1,331 @@
+//! Recording every raw datagram of a session to a capture file and replaying it.
+//!
+//! The capture file starts with the `MAGIC` and `VERSION`, followed by the records:
+//!
+//! * `u8` - the `Direction`
+//! * `i64` - the timestamp in microseconds since the UNIX epoch
+//! * `u32` - the length of the datagram
+//! * the datagram bytes
+//!
+//! All numbers are in little endian.
+//!
+//! ```ignore
+//! use arsdk_rs::{prelude::*, record::{Recorder, Replayer}};
+//!
+//! let config = Config::builder(PARROT_SPHINX_IP)
+//!     .recorder(Recorder::create("flight.arsdk")?)
+//!     .build()?;
+//!
+//! // ... fly ...
+//!
+//! for record in Replayer::open("flight.arsdk")? {
+//!     let record = record?;
+//!     println!("{:?}: {:?}", record.direction, record.frames());
+//! }
+//! ```
+use crate::{
+    frame::{Error, FrameType},
+    parse::parse_message_frames,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use log::error;
+use scroll::{Pread, Pwrite, LE};
+use std::{
+    convert::TryFrom,
+    error, fmt,
+    fs::File,
+    io::{self, BufReader, BufWriter, ErrorKind, Read, Write},
+    path::Path,
+    sync::{Mutex, PoisonError},
+};
+
+pub const MAGIC: &[u8; 8] = b"ARSDKREC";
+pub const VERSION: u8 = 1;
+
+/// Direction, timestamp and length
+const RECORD_HEADER_LEN: usize = 1 + 8 + 4;
+/// The records are UDP datagrams, protects from allocating a huge buffer for a corrupted length
+const MAX_RECORD_LEN: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Received from the Drone
+    Incoming = 0,
+    /// Sent to the Drone
+    Outgoing = 1,
+}
+
+/// A single datagram of the session
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub direction: Direction,
+    pub timestamp: DateTime<Utc>,
+    pub data: Vec<u8>,
+}
+
+impl Record {
+    /// Parses the frames of the datagram the same way as the `Listener`
+    pub fn frames(&self) -> Vec<Result<FrameType, Error>> {
+        parse_message_frames(&self.data)
+    }
+}
+
+/// Writes the records to a capture file, use it for `Config::recorder`
+pub struct Recorder {
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+
+impl fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recorder").finish()
+    }
+}
+
+impl Recorder {
+    /// Creates (or truncates) the capture file
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?))
+    }
+
+    /// Writes the `MAGIC` and `VERSION` to the writer
+    pub fn new(mut writer: impl Write + Send + 'static) -> io::Result<Self> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+
+        Ok(Self {
+            writer: Mutex::new(Box::new(writer)),
+        })
+    }
+
+    /// Records the datagram with the current time
+    pub fn record(&self, direction: Direction, data: &[u8]) -> io::Result<()> {
+        self.write(&Record {
+            direction,
+            timestamp: Utc::now(),
+            data: data.to_vec(),
+        })
+    }
+
+    pub fn write(&self, record: &Record) -> io::Result<()> {
+        // the `Replayer` rejects longer records
+        let length = u32::try_from(record.data.len())
+            .ok()
+            .filter(|_| record.data.len() <= MAX_RECORD_LEN)
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidInput, "Datagram is too long"))?;
+        let timestamp = record.timestamp.timestamp() * 1_000_000
+            + i64::from(record.timestamp.timestamp_subsec_micros());
+
+        let mut header = [0_u8; RECORD_HEADER_LEN];
+        let mut offset = 0;
+        header
+            .gwrite_with::<u8>(record.direction as u8, &mut offset, LE)
+            .and_then(|_| header.gwrite_with::<i64>(timestamp, &mut offset, LE))
+            .and_then(|_| header.gwrite_with::<u32>(length, &mut offset, LE))
+            .map_err(|err| io::Error::new(ErrorKind::InvalidInput, err))?;
+
+        let mut writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        writer.write_all(&header)?;
+        writer.write_all(&record.data)?;
+        // a crash in the field shouldn't lose the session
+        writer.flush()
+    }
+}
+
+/// Records the datagram if there's a `Recorder`, logging the errors
+pub(crate) fn record(recorder: Option<&Recorder>, direction: Direction, data: &[u8]) {
+    if let Some(recorder) = recorder {
+        if let Err(err) = recorder.record(direction, data) {
+            error!("Recording {:?} datagram: {}", direction, err);
+        }
+    }
+}
+
+/// Reads the records of a capture file
+pub struct Replayer<R> {
+    reader: R,
+}
+
+impl Replayer<BufReader<File>> {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> Replayer<R> {
+    /// Checks the `MAGIC` and `VERSION` of the capture
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0_u8; 9];
+        reader.read_exact(&mut magic)?;
+
+        if &magic[..8] != MAGIC || magic[8] != VERSION {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "Not an arsdk-rs capture file",
+            ));
+        }
+
+        Ok(Self { reader })
+    }
+
+    fn read_record(&mut self) -> io::Result<Option<Record>> {
+        let mut header = [0_u8; RECORD_HEADER_LEN];
+
+        // the end of the capture
+        match self.reader.read(&mut header[..1])? {
+            0 => return Ok(None),
+            _ => self.reader.read_exact(&mut header[1..])?,
+        }
+
+        let mut offset = 0;
+        let direction = match header.gread_with::<u8>(&mut offset, LE) {
+            Ok(0) => Direction::Incoming,
+            Ok(1) => Direction::Outgoing,
+            _ => return Err(invalid_data("Unknown direction")),
+        };
+        let timestamp = header
+            .gread_with::<i64>(&mut offset, LE)
+            .map_err(invalid_data)?;
+        let length = header
+            .gread_with::<u32>(&mut offset, LE)
+            .map_err(invalid_data)?;
+
+        let timestamp = Utc
+            .timestamp_opt(
+                timestamp.div_euclid(1_000_000),
+                (timestamp.rem_euclid(1_000_000) * 1_000) as u32,
+            )
+            .single()
+            .ok_or_else(|| invalid_data("Invalid timestamp"))?;
+
+        let mut data = vec![0_u8; checked_len(length)?];
+        self.reader
+            .read_exact(&mut data)
+            .map_err(|err| match err.kind() {
+                ErrorKind::UnexpectedEof => invalid_data("Truncated record"),
+                _ => err,
+            })?;
+
+        Ok(Some(Record {
+            direction,
+            timestamp,
+            data,
+        }))
+    }
+}
+
+fn checked_len(len: u32) -> io::Result<usize> {
+    match len as usize {
+        len if len <= MAX_RECORD_LEN => Ok(len),
+        _ => Err(invalid_data("Record is too long")),
+    }
+}
+
+pub(crate) fn invalid_data(err: impl Into<Box<dyn error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, err)
+}
+
+impl<R: Read> Iterator for Replayer<R> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frame::{BufferID, Frame, Type};
+    use std::sync::Arc;
+
+    /// Shared buffer, so we can read what the `Recorder` wrote
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let capture = Capture::default();
+        let recorder = Recorder::new(capture.clone()).expect("Should write the header");
+
+        let ack = [1, 139, 0, 8, 0, 0, 0, 0];
+        let take_off = [4, 11, 1, 11, 0, 0, 0, 1, 0, 1, 0];
+        recorder
+            .record(Direction::Incoming, &ack)
+            .expect("Should record");
+        recorder
+            .record(Direction::Outgoing, &take_off)
+            .expect("Should record");
+
+        let bytes = capture.0.lock().unwrap().clone();
+        let records = Replayer::new(bytes.as_slice())
+            .expect("Should be a capture")
+            .collect::<io::Result<Vec<_>>>()
+            .expect("Should read the records");
+
+        assert_eq!(2, records.len());
+        assert_eq!(Direction::Incoming, records[0].direction);
+        assert_eq!(ack.to_vec(), records[0].data);
+        assert_eq!(Direction::Outgoing, records[1].direction);
+        assert!(records[0].timestamp <= records[1].timestamp);
+
+        match records[0].frames().pop() {
+            Some(Ok(FrameType::Known(Frame {
+                frame_type: Type::Ack,
+                buffer_id: BufferID::ACKFromSendWithAck,
+                ..
+            }))) => {}
+            frame => panic!("Unexpected frame: {:?}", frame),
+        }
+    }
+
+    #[test]
+    fn test_not_a_capture() {
+        assert!(Replayer::new(&b"not a capture"[..]).is_err());
+    }
+
+    fn capture_with_record(length: u32, data: &[u8]) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        // Incoming at the epoch
+        bytes.push(0);
+        bytes.extend_from_slice(&0_i64.to_le_bytes());
+        bytes.extend_from_slice(&length.to_le_bytes());
+        bytes.extend_from_slice(data);
+
+        bytes
+    }
+
+    #[test]
+    fn test_truncated_record() {
+        let bytes = capture_with_record(8, &[1, 139, 0]);
+        let mut replayer = Replayer::new(bytes.as_slice()).expect("Should be a capture");
+
+        let err = replayer
+            .next()
+            .expect("Should read the record")
+            .expect_err("Should be truncated");
+        assert_eq!(ErrorKind::InvalidData, err.kind());
+    }
+
+    #[test]
+    fn test_too_long_record() {
+        let bytes = capture_with_record(u32::MAX, &[1, 139, 0]);
+        let mut replayer = Replayer::new(bytes.as_slice()).expect("Should be a capture");
+
+        let err = replayer
+            .next()
+            .expect("Should read the record")
+            .expect_err("Should be too long");
+        assert_eq!(ErrorKind::InvalidData, err.kind());
+    }
+}
@@ -0,0 +1,188 @@
+//! Talking to a Drone through a SkyController, the way FreeFlight does.
+//!
+//! The SkyController connects to the Drone over its own Wifi and relays every frame
+//! between the Drone and us, so once it's connected to a Drone the `Drone` returned
+//! by `SkyController::drone()` is used as if it was connected to the Drone directly.
+//!
+//! ```no_run
+//! use arsdk_rs::{relay::SkyController, SKYCONTROLLER_CONFIG};
+//! use std::time::Duration;
+//!
+//! let sky_controller = SkyController::connect(SKYCONTROLLER_CONFIG)?;
+//! sky_controller.connect_to_device("Bebop2-123456")?.wait()?;
+//!
+//! match sky_controller.wait_for_device(Duration::from_secs(30))? {
+//!     Some(name) => println!("Relaying to {}", name),
+//!     None => println!("The SkyController didn't connect to the Drone"),
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+use crate::{
+    command::Feature,
+    frame::{BufferID, Frame, FrameType, Type},
+    skycontroller::{
+        co_piloting::{self, SetPilotingSource},
+        device::{self, ConnectToDevice},
+        device_state::{self, ConnexionChanged, ConnexionChangedStatus},
+        wifi_list, Class,
+    },
+    subscription::Filter,
+    AckHandle, Config, ConnectionError, Drone, Error,
+};
+use std::{
+    sync::mpsc::RecvTimeoutError,
+    time::{Duration, Instant},
+};
+
+pub use crate::skycontroller::co_piloting::SetPilotingSourceSource as PilotingSource;
+
+/// A connection to a SkyController (2)
+#[derive(Debug, Clone)]
+pub struct SkyController {
+    drone: Drone,
+}
+
+impl SkyController {
+    /// Connects to the SkyController, e.g. with `SKYCONTROLLER_CONFIG`
+    pub fn connect(config: Config) -> Result<Self, ConnectionError> {
+        Ok(Self::from_drone(Drone::connect(config)?))
+    }
+
+    /// Uses an existing connection to the SkyController
+    pub fn from_drone(drone: Drone) -> Self {
+        Self { drone }
+    }
+
+    /// The connection to the SkyController, the frames sent with it are relayed to the paired Drone
+    pub fn drone(&self) -> &Drone {
+        &self.drone
+    }
+
+    /// Stops the background threads of the `Drone`, see `Drone::disconnect()`
+    pub fn disconnect(&self) {
+        self.drone.disconnect()
+    }
+
+    /// The SkyController answers with a `DeviceState::DeviceList` for each visible Drone
+    pub fn request_device_list(&self) -> Result<AckHandle, Error> {
+        self.send(Class::Device(device::Command::RequestDeviceList))
+    }
+
+    /// The SkyController answers with a `DeviceState::ConnexionChanged`
+    pub fn request_current_device(&self) -> Result<AckHandle, Error> {
+        self.send(Class::Device(device::Command::RequestCurrentDevice))
+    }
+
+    pub fn connect_to_device(&self, device_name: &str) -> Result<AckHandle, Error> {
+        self.send(Class::Device(device::Command::ConnectToDevice(
+            ConnectToDevice {
+                device_name: device_name.to_string(),
+            },
+        )))
+    }
+
+    /// The SkyController answers with a `WifiState::WifiList` for each visible network
+    pub fn request_wifi_list(&self) -> Result<AckHandle, Error> {
+        self.send(Class::WifiList(wifi_list::Command::RequestWifiList))
+    }
+
+    /// Whether the sticks of the SkyController or our PCMDs pilot the Drone
+    pub fn set_piloting_source(&self, source: PilotingSource) -> Result<AckHandle, Error> {
+        self.send(Class::CoPiloting(co_piloting::Command::SetPilotingSource(
+            SetPilotingSource { source },
+        )))
+    }
+
+    /// Waits until the SkyController is connected to a Drone and returns its name,
+    /// `None` if it's not connected before the `timeout`
+    pub fn wait_for_device(&self, timeout: Duration) -> Result<Option<String>, Error> {
+        let deadline = Instant::now() + timeout;
+        let device_states = self.drone.subscribe(Filter::Class {
+            feature: 4,
+            class: device_state::CLASS_ID,
+        });
+        self.request_current_device()?;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            match device_states.recv_timeout(remaining) {
+                Ok(frame_type) => {
+                    if let Some(device_name) = connected_device(&frame_type) {
+                        return Ok(Some(device_name));
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => {
+                    return Ok(None)
+                }
+            }
+        }
+    }
+
+    fn send(&self, class: Class) -> Result<AckHandle, Error> {
+        let frame = Frame::for_drone(
+            &self.drone,
+            Type::DataWithAck,
+            BufferID::CDAck,
+            Some(Feature::SkyController(Some(class))),
+        );
+
+        self.drone.send_frame(frame)
+    }
+}
+
+/// The name of the Drone if the frame is a `DeviceState::ConnexionChanged` to `CONNECTED`
+fn connected_device(frame_type: &FrameType) -> Option<String> {
+    match frame_type {
+        FrameType::Known(Frame {
+            feature:
+                Some(Feature::SkyController(Some(Class::DeviceState(
+                    device_state::Command::ConnexionChanged(ConnexionChanged {
+                        status: ConnexionChangedStatus::Connected,
+                        device_name,
+                        ..
+                    }),
+                )))),
+            ..
+        }) => Some(device_name.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scroll::{Pread, LE};
+
+    #[test]
+    fn test_connected_device() {
+        //                    type buf   seq  [     len        ] [SC  DS   Conn     CONNECTED          ]
+        let connected: [u8; 31] = [
+            0x4, 0x7e, 0x1, 0x1f, 0x0, 0x0, 0x0, 0x4, 0x3, 0x1, 0x0, 0x2, 0x0, 0x0, 0x0,
+            // Bebop2-123456
+            0x42, 0x65, 0x62, 0x6f, 0x70, 0x32, 0x2d, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x0,
+            // product id
+            0x0c, 0x09,
+        ];
+        let frame = connected
+            .pread_with::<Frame>(0, LE)
+            .expect("Should deserialize");
+
+        assert_eq!(
+            Some("Bebop2-123456".to_string()),
+            connected_device(&FrameType::Known(frame.clone()))
+        );
+
+        let connecting = Frame {
+            feature: Some(Feature::SkyController(Some(Class::DeviceState(
+                device_state::Command::ConnexionChanged(ConnexionChanged {
+                    status: ConnexionChangedStatus::Connecting,
+                    device_name: "Bebop2-123456".to_string(),
+                    device_product_id: 0x090c,
+                }),
+            )))),
+            ..frame
+        };
+        assert_eq!(None, connected_device(&FrameType::Known(connecting)));
+    }
+}
@@ -0,0 +1,223 @@
+use crate::{
+    ardrone3::{ArDrone3, FlyingState, GPSState, PilotingState},
+    command::Feature,
+    common::{self, CommonState},
+};
+use chrono::{DateTime, Utc};
+
+/// The value which the Drone sends for not available latitude, longitude and altitude
+const NOT_AVAILABLE: f64 = 500.0;
+
+/// Snapshot of the latest known state of the Drone.
+///
+/// It's updated from the incoming events of the Drone, each field is `None`
+/// until the Drone sends the corresponding event.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DroneState {
+    /// From `PilotingState::FlyingStateChanged`
+    pub flying_state: Option<FlyingState>,
+    /// Battery charge in percent, from `CommonState::BatteryStateChanged`
+    pub battery: Option<u8>,
+    /// From `PilotingState::PositionChanged` or `PilotingState::GpsLocationChanged`
+    pub position: Option<Position>,
+    /// From `PilotingState::AttitudeChanged`
+    pub attitude: Option<Attitude>,
+    /// From `PilotingState::SpeedChanged`
+    pub speed: Option<Speed>,
+    /// Altitude in meters relative to the take off point, from `PilotingState::AltitudeChanged`
+    pub altitude: Option<f64>,
+    /// Number of GPS satellites, from `GPSState::NumberOfSatelliteChanged`
+    pub satellites: Option<u8>,
+    /// RSSI of the Wifi signal in dbm, from `CommonState::WifiSignalChanged`
+    pub wifi_rssi: Option<i16>,
+    /// When the last event updating the state was received
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// GPS position of the Drone
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    /// In decimal degrees
+    pub latitude: f64,
+    /// In decimal degrees
+    pub longitude: f64,
+    /// In meters, `None` if not available
+    pub altitude: Option<f64>,
+}
+
+/// Angles in radians
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Attitude {
+    pub roll: f32,
+    pub pitch: f32,
+    pub yaw: f32,
+}
+
+/// Speed in m/s
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Speed {
+    /// Relative to the North
+    pub x: f32,
+    /// Relative to the East
+    pub y: f32,
+    /// Relative to the ground, positive means going down
+    pub z: f32,
+}
+
+impl DroneState {
+    /// Folds the event into the state, returns `true` if the state was updated
+    pub fn update(&mut self, feature: &Feature) -> bool {
+        let updated = match feature {
+            Feature::ArDrone3(Some(ArDrone3::PilotingState(piloting_state))) => {
+                self.update_piloting_state(piloting_state)
+            }
+            Feature::ArDrone3(Some(ArDrone3::GPSState(GPSState::NumberOfSatelliteChanged(
+                satellites,
+            )))) => {
+                self.satellites = Some(*satellites);
+                true
+            }
+            Feature::Common(Some(common::Class::CommonState(common_state))) => {
+                self.update_common_state(common_state)
+            }
+            _ => false,
+        };
+
+        if updated {
+            self.updated_at = Some(Utc::now());
+        }
+
+        updated
+    }
+
+    fn update_piloting_state(&mut self, piloting_state: &PilotingState) -> bool {
+        match *piloting_state {
+            PilotingState::FlyingStateChanged(flying_state) => {
+                self.flying_state = Some(flying_state);
+            }
+            PilotingState::PositionChanged {
+                latitude,
+                longitude,
+                altitude,
+            }
+            | PilotingState::GpsLocationChanged {
+                latitude,
+                longitude,
+                altitude,
+                ..
+            } => {
+                self.position = Position::new(latitude, longitude, altitude);
+            }
+            PilotingState::SpeedChanged {
+                speed_x,
+                speed_y,
+                speed_z,
+            } => {
+                self.speed = Some(Speed {
+                    x: speed_x,
+                    y: speed_y,
+                    z: speed_z,
+                });
+            }
+            PilotingState::AttitudeChanged { roll, pitch, yaw } => {
+                self.attitude = Some(Attitude { roll, pitch, yaw });
+            }
+            PilotingState::AltitudeChanged(altitude) => {
+                self.altitude = Some(altitude);
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn update_common_state(&mut self, common_state: &CommonState) -> bool {
+        match *common_state {
+            CommonState::BatteryStateChanged(percent) => self.battery = Some(percent),
+            CommonState::WifiSignalChanged(rssi) => self.wifi_rssi = Some(rssi),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl Position {
+    /// Returns `None` if the latitude or longitude are not available
+    fn new(latitude: f64, longitude: f64, altitude: f64) -> Option<Self> {
+        #[allow(clippy::float_cmp)]
+        let available = |value: f64| value != NOT_AVAILABLE;
+
+        if available(latitude) && available(longitude) {
+            Some(Self {
+                latitude,
+                longitude,
+                altitude: Some(altitude).filter(|altitude| available(*altitude)),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn piloting_state(piloting_state: PilotingState) -> Feature {
+        Feature::ArDrone3(Some(ArDrone3::PilotingState(piloting_state)))
+    }
+
+    #[test]
+    fn test_update() {
+        let mut state = DroneState::default();
+
+        assert!(
+            state.update(&piloting_state(PilotingState::FlyingStateChanged(
+                FlyingState::Hovering
+            )))
+        );
+        assert!(
+            state.update(&Feature::Common(Some(common::Class::CommonState(
+                CommonState::BatteryStateChanged(87)
+            ))))
+        );
+        assert!(state.update(&Feature::ArDrone3(Some(ArDrone3::GPSState(
+            GPSState::NumberOfSatelliteChanged(12)
+        )))));
+        assert!(state.update(&piloting_state(PilotingState::AltitudeChanged(2.5))));
+        assert!(!state.update(&Feature::ArDrone3(None)));
+
+        assert_eq!(Some(FlyingState::Hovering), state.flying_state);
+        assert_eq!(Some(87), state.battery);
+        assert_eq!(Some(12), state.satellites);
+        assert_eq!(Some(2.5), state.altitude);
+        assert!(state.updated_at.is_some());
+    }
+
+    #[test]
+    fn test_not_available_position() {
+        let mut state = DroneState::default();
+
+        state.update(&piloting_state(PilotingState::PositionChanged {
+            latitude: 48.8,
+            longitude: 2.3,
+            altitude: NOT_AVAILABLE,
+        }));
+        assert_eq!(
+            Some(Position {
+                latitude: 48.8,
+                longitude: 2.3,
+                altitude: None
+            }),
+            state.position
+        );
+
+        state.update(&piloting_state(PilotingState::PositionChanged {
+            latitude: NOT_AVAILABLE,
+            longitude: NOT_AVAILABLE,
+            altitude: NOT_AVAILABLE,
+        }));
+        assert_eq!(None, state.position);
+    }
+}
@@ -0,0 +1,499 @@
+use crate::frame::{BufferID, FrameType, Type};
+use log::warn;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{sync_channel, Receiver, RecvError, RecvTimeoutError, SyncSender, TrySendError},
+        Arc, Mutex, PoisonError,
+    },
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+pub use std::sync::mpsc::TryRecvError;
+
+/// How many frames are buffered for each `Subscription` by default
+pub const DEFAULT_CAPACITY: usize = 200;
+
+/// How long a `Policy::Block` subscriber is waited for before the frame is dropped
+pub const BLOCK_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How often the buffer of a `Policy::Block` subscriber is checked while waiting
+const BLOCK_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// The smallest buffer of a `Subscription`, a `0` capacity would make every send wait
+/// for the subscriber to receive the frame.
+pub const MIN_CAPACITY: usize = 1;
+
+/// Which of the incoming frames should be delivered to a `Subscription`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Filter {
+    /// Every incoming frame, including the `FrameType::Unknown` ones, PINGs and Acks
+    All,
+    /// Frames of a `command::Feature`, e.g. `1` for `Feature::ArDrone3`
+    Feature(u8),
+    /// Frames of a single class of a `command::Feature`,
+    /// e.g. `Filter::Class { feature: 1, class: 4 }` for `ArDrone3::PilotingState`
+    Class { feature: u8, class: u8 },
+}
+
+impl Filter {
+    pub fn matches(&self, frame_type: &FrameType) -> bool {
+        let frame = match (self, frame_type) {
+            (Filter::All, _) => return true,
+            (_, FrameType::Unknown(_)) => return false,
+            (_, FrameType::Known(frame)) => frame,
+        };
+
+        // PINGs and Acks don't carry a Feature, even if it's parsed as one
+        if frame.frame_type == Type::Ack
+            || [BufferID::PING, BufferID::PONG].contains(&frame.buffer_id)
+        {
+            return false;
+        }
+
+        let feature = match frame.feature.as_ref() {
+            Some(feature) => feature,
+            None => return false,
+        };
+
+        let feature_id: u8 = feature.into();
+
+        match *self {
+            Filter::All => true,
+            Filter::Feature(filter_feature) => filter_feature == feature_id,
+            Filter::Class {
+                feature: filter_feature,
+                class,
+            } => filter_feature == feature_id && Some(class) == feature.class_id(),
+        }
+    }
+}
+
+/// What happens when the buffer of a `Subscription` is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    /// The new frame is not delivered to the `Subscription`, see `Subscription::dropped()`
+    DropNewest,
+    /// Waits up to `BLOCK_TIMEOUT` for the `Subscription` to receive a frame,
+    /// then the new frame is not delivered, see `Subscription::dropped()`.
+    ///
+    /// It stops waiting as soon as the link is closed or the `Drone` is disconnected.
+    ///
+    /// **NOTE:** This blocks the Listener, so no frames are handled
+    /// (including PINGs and Acks) in the meantime!
+    Block,
+    /// The `Subscription` is disconnected and won't receive any more frames
+    Unsubscribe,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::DropNewest
+    }
+}
+
+/// Receives the decoded incoming frames which match the `Filter`.
+///
+/// Dropping the `Subscription` unsubscribes it.
+#[derive(Debug)]
+pub struct Subscription {
+    receiver: Receiver<FrameType>,
+    dropped: Arc<AtomicUsize>,
+}
+
+impl Subscription {
+    /// Blocks until a frame is received
+    pub fn recv(&self) -> Result<FrameType, RecvError> {
+        self.receiver.recv()
+    }
+
+    pub fn try_recv(&self) -> Result<FrameType, TryRecvError> {
+        self.receiver.try_recv()
+    }
+
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<FrameType, RecvTimeoutError> {
+        self.receiver.recv_timeout(timeout)
+    }
+
+    /// How many frames were not delivered because the buffer was full
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl Iterator for Subscription {
+    type Item = FrameType;
+
+    /// Blocks until a frame is received
+    fn next(&mut self) -> Option<Self::Item> {
+        self.recv().ok()
+    }
+}
+
+/// A `Stream` of the decoded incoming frames which match the `Filter`.
+///
+/// Frames are never awaited for, if the buffer is full the new frames are dropped,
+/// see `FrameStream::dropped()`.
+/// Dropping the `FrameStream` unsubscribes it.
+#[cfg(feature = "tokio")]
+#[derive(Debug)]
+pub struct FrameStream {
+    receiver: tokio::sync::mpsc::Receiver<FrameType>,
+    dropped: Arc<AtomicUsize>,
+}
+
+#[cfg(feature = "tokio")]
+impl FrameStream {
+    /// How many frames were not delivered because the buffer was full
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl tokio::stream::Stream for FrameStream {
+    type Item = FrameType;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// The sending side of a `Subscription` or a `FrameStream`
+#[derive(Debug)]
+enum Sink {
+    Blocking(SyncSender<FrameType>),
+    #[cfg(feature = "tokio")]
+    Tokio(tokio::sync::mpsc::Sender<FrameType>),
+}
+
+#[derive(Debug)]
+struct Subscriber {
+    filter: Filter,
+    policy: Policy,
+    sink: Sink,
+    dropped: Arc<AtomicUsize>,
+}
+
+/// A frame for a `Policy::Block` subscriber whose buffer was full
+#[derive(Debug)]
+struct Blocked {
+    sender: SyncSender<FrameType>,
+    frame_type: FrameType,
+    dropped: Arc<AtomicUsize>,
+}
+
+impl Blocked {
+    /// Re-tries sending the frame until `BLOCK_TIMEOUT` or `keep_waiting` returns `false`
+    fn wait(self, keep_waiting: &impl Fn() -> bool) {
+        let deadline = Instant::now() + BLOCK_TIMEOUT;
+        let mut frame_type = self.frame_type;
+
+        while Instant::now() < deadline && keep_waiting() {
+            match self.sender.try_send(frame_type) {
+                // disconnected subscribers are removed on the next publish
+                Ok(_) | Err(TrySendError::Disconnected(_)) => return,
+                Err(TrySendError::Full(not_sent)) => frame_type = not_sent,
+            }
+
+            sleep(BLOCK_POLL_INTERVAL);
+        }
+
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Subscriber {
+    /// Returns `false` if the subscriber should be removed
+    fn deliver(&mut self, frame_type: &FrameType, blocked: &mut Vec<Blocked>) -> bool {
+        if !self.filter.matches(frame_type) {
+            return true;
+        }
+
+        let result = match &mut self.sink {
+            Sink::Blocking(sender) => sender.try_send(frame_type.clone()),
+            #[cfg(feature = "tokio")]
+            Sink::Tokio(sender) => {
+                use tokio::sync::mpsc::error::TrySendError as TokioTrySendError;
+
+                sender
+                    .try_send(frame_type.clone())
+                    .map_err(|err| match err {
+                        TokioTrySendError::Full(frame) => TrySendError::Full(frame),
+                        TokioTrySendError::Closed(frame) => TrySendError::Disconnected(frame),
+                    })
+            }
+        };
+
+        match result {
+            Ok(_) => true,
+            Err(TrySendError::Full(frame_type)) => match (&self.sink, self.policy) {
+                (Sink::Blocking(sender), Policy::Block) => {
+                    blocked.push(Blocked {
+                        sender: sender.clone(),
+                        frame_type,
+                        dropped: self.dropped.clone(),
+                    });
+                    true
+                }
+                (_, Policy::Unsubscribe) => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    warn!("Unsubscribing slow subscriber with {:?}", self.filter);
+                    false
+                }
+                _ => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    true
+                }
+            },
+            Err(TrySendError::Disconnected(_)) => false,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Subscribers {
+    subscribers: Mutex<Vec<Subscriber>>,
+}
+
+impl Subscribers {
+    pub(crate) fn subscribe(
+        &self,
+        filter: Filter,
+        capacity: usize,
+        policy: Policy,
+    ) -> Subscription {
+        let (sender, receiver) = sync_channel(capacity.max(MIN_CAPACITY));
+        let dropped = self.push(filter, policy, Sink::Blocking(sender));
+
+        Subscription { receiver, dropped }
+    }
+
+    #[cfg(feature = "tokio")]
+    pub(crate) fn subscribe_stream(&self, filter: Filter, capacity: usize) -> FrameStream {
+        let (sender, receiver) = tokio::sync::mpsc::channel(capacity.max(MIN_CAPACITY));
+        let dropped = self.push(filter, Policy::DropNewest, Sink::Tokio(sender));
+
+        FrameStream { receiver, dropped }
+    }
+
+    fn push(&self, filter: Filter, policy: Policy, sink: Sink) -> Arc<AtomicUsize> {
+        let dropped = Arc::new(AtomicUsize::new(0));
+
+        self.subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Subscriber {
+                filter,
+                policy,
+                sink,
+                dropped: dropped.clone(),
+            });
+
+        dropped
+    }
+
+    /// Delivers the frame to every matching `Subscription` and `FrameStream`.
+    ///
+    /// The full `Policy::Block` subscribers are waited for without holding the lock,
+    /// until `keep_waiting` returns `false`.
+    pub(crate) fn publish(&self, frame_type: &FrameType, keep_waiting: impl Fn() -> bool) {
+        let mut blocked = Vec::new();
+
+        {
+            let mut subscribers = self
+                .subscribers
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+
+            // instead of `Vec::retain_mut`, which needs Rust 1.61
+            let mut index = 0;
+            while index < subscribers.len() {
+                if subscribers[index].deliver(frame_type, &mut blocked) {
+                    index += 1;
+                } else {
+                    subscribers.remove(index);
+                }
+            }
+        }
+
+        for blocked in blocked {
+            blocked.wait(&keep_waiting);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        ardrone3::{ArDrone3, Piloting},
+        command::Feature,
+        frame::Frame,
+    };
+
+    fn frame(buffer_id: BufferID, feature: Feature) -> FrameType {
+        FrameType::Known(Frame {
+            frame_type: Type::DataWithAck,
+            buffer_id,
+            sequence_id: 0,
+            feature: Some(feature),
+        })
+    }
+
+    #[test]
+    fn test_filter() {
+        let take_off = frame(
+            BufferID::DCEvent,
+            Feature::ArDrone3(Some(ArDrone3::Piloting(Piloting::TakeOff))),
+        );
+        let ping = frame(
+            BufferID::PING,
+            Feature::Unknown {
+                feature: 1,
+                data: vec![],
+            },
+        );
+
+        assert!(Filter::All.matches(&take_off));
+        assert!(Filter::All.matches(&ping));
+
+        assert!(Filter::Feature(1).matches(&take_off));
+        assert!(!Filter::Feature(0).matches(&take_off));
+        assert!(!Filter::Feature(1).matches(&ping));
+
+        assert!(Filter::Class {
+            feature: 1,
+            class: 0
+        }
+        .matches(&take_off));
+        assert!(!Filter::Class {
+            feature: 1,
+            class: 4
+        }
+        .matches(&take_off));
+    }
+
+    #[test]
+    fn test_slow_subscriber_policies() {
+        let subscribers = Subscribers::default();
+        let take_off = frame(
+            BufferID::DCEvent,
+            Feature::ArDrone3(Some(ArDrone3::Piloting(Piloting::TakeOff))),
+        );
+
+        let drop_newest = subscribers.subscribe(Filter::All, 1, Policy::DropNewest);
+        let unsubscribe = subscribers.subscribe(Filter::All, 1, Policy::Unsubscribe);
+        let other_feature = subscribers.subscribe(Filter::Feature(0), 1, Policy::Unsubscribe);
+
+        subscribers.publish(&take_off, || true);
+        subscribers.publish(&take_off, || true);
+
+        assert_eq!(Ok(take_off.clone()), drop_newest.try_recv());
+        assert_eq!(Err(TryRecvError::Empty), drop_newest.try_recv());
+        assert_eq!(1, drop_newest.dropped());
+
+        assert_eq!(Ok(take_off.clone()), unsubscribe.try_recv());
+        assert_eq!(Err(TryRecvError::Disconnected), unsubscribe.try_recv());
+
+        assert_eq!(Err(TryRecvError::Empty), other_feature.try_recv());
+
+        // dropped subscriptions are removed on the next publish
+        drop(drop_newest);
+        subscribers.publish(&take_off, || true);
+        assert_eq!(
+            1,
+            subscribers
+                .subscribers
+                .lock()
+                .expect("Should lock subscribers")
+                .len()
+        );
+    }
+
+    #[test]
+    fn test_zero_capacity_is_raised() {
+        let subscribers = Subscribers::default();
+        let take_off = frame(
+            BufferID::DCEvent,
+            Feature::ArDrone3(Some(ArDrone3::Piloting(Piloting::TakeOff))),
+        );
+
+        let block = subscribers.subscribe(Filter::All, 0, Policy::Block);
+
+        // buffered without waiting for the subscriber to receive it
+        let started = Instant::now();
+        subscribers.publish(&take_off, || true);
+        assert!(started.elapsed() < BLOCK_TIMEOUT);
+
+        assert_eq!(Ok(take_off), block.try_recv());
+        assert_eq!(0, block.dropped());
+    }
+
+    #[test]
+    fn test_block_policy_gives_up() {
+        let subscribers = Subscribers::default();
+        let take_off = frame(
+            BufferID::DCEvent,
+            Feature::ArDrone3(Some(ArDrone3::Piloting(Piloting::TakeOff))),
+        );
+
+        let block = subscribers.subscribe(Filter::All, 1, Policy::Block);
+
+        subscribers.publish(&take_off, || true);
+        // the link is closed, so it doesn't wait for the full subscriber
+        let started = Instant::now();
+        subscribers.publish(&take_off, || false);
+        assert!(started.elapsed() < BLOCK_TIMEOUT);
+        assert_eq!(1, block.dropped());
+
+        // the lock isn't held while waiting, so subscribing doesn't block
+        let subscribers = Arc::new(subscribers);
+        let publishing = std::thread::spawn({
+            let subscribers = subscribers.clone();
+            let take_off = take_off.clone();
+
+            move || subscribers.publish(&take_off, || true)
+        });
+
+        sleep(BLOCK_POLL_INTERVAL * 4);
+        let _other = subscribers.subscribe(Filter::All, 1, Policy::DropNewest);
+
+        assert_eq!(Ok(take_off.clone()), block.recv_timeout(BLOCK_TIMEOUT));
+        assert_eq!(Ok(take_off), block.recv_timeout(BLOCK_TIMEOUT));
+        publishing.join().expect("Should publish");
+        assert_eq!(1, block.dropped());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_frame_stream() {
+        use tokio::stream::StreamExt;
+
+        let subscribers = Subscribers::default();
+        let take_off = frame(
+            BufferID::DCEvent,
+            Feature::ArDrone3(Some(ArDrone3::Piloting(Piloting::TakeOff))),
+        );
+
+        let mut stream = subscribers.subscribe_stream(Filter::Feature(1), 1);
+
+        subscribers.publish(&take_off, || true);
+        subscribers.publish(&take_off, || true);
+        assert_eq!(1, stream.dropped());
+
+        let mut runtime = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .build()
+            .expect("Should build runtime");
+
+        assert_eq!(Some(take_off), runtime.block_on(stream.next()));
+
+        drop(subscribers);
+        assert_eq!(None, runtime.block_on(stream.next()));
+    }
+}
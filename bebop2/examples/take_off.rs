@@ -22,7 +22,8 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let drone = Bebop2::connect(PARROT_SPHINX_CONFIG)?;
 
     info!("Takeoff!");
-    drone.take_off()?;
+    drone.take_off()?.await?;
+    info!("Takeoff acknowledged by the drone");
 
     info!("Wait 5 seconds and fly UP");
     delay_for(Duration::from_secs(5)).await;
@@ -1,6 +1,7 @@
 use arsdk_rs::{
     command::Feature,
     frame::{BufferID, Frame, Type},
+    relay::SkyController,
 };
 
 pub use arsdk_rs::{
@@ -27,8 +28,24 @@ impl Bebop2 {
         Ok(Self { drone })
     }
 
+    /// Pilots the Bebop2 paired with the SkyController, the commands are relayed by it.
+    ///
+    /// Wait for `SkyController::wait_for_device()` before sending commands.
+    pub fn through(sky_controller: &SkyController) -> Self {
+        Self {
+            drone: sky_controller.drone().clone(),
+        }
+    }
+
+    /// Stops the background threads of the `Drone`, see `Drone::disconnect()`
+    pub fn disconnect(&self) {
+        self.drone.disconnect()
+    }
+
     /// - Captain #Ferris 🦀 :Take off... 🛫
-    pub fn take_off(&self) -> Result<(), Error> {
+    ///
+    /// Wait on the returned `AckHandle` to know that the Drone accepted the command.
+    pub fn take_off(&self) -> Result<AckHandle, Error> {
         let feature = Feature::ArDrone3(Some(ArDrone3::Piloting(Piloting::TakeOff)));
 
         let frame = Frame::for_drone(
@@ -41,8 +58,8 @@ impl Bebop2 {
         self.drone.send_frame(frame)
     }
 
-    pub fn up(&self, sequence_id: u8) -> Result<(), Error> {
-        let feature = Feature::ArDrone3(Some(ArDrone3::Piloting(Piloting::PCMD(PCMD {
+    pub fn up(&self, sequence_id: u8) -> Result<AckHandle, Error> {
+        self.pcmd(PCMD {
             flag: true,
             roll: 0,
             pitch: 0,
@@ -50,15 +67,11 @@ impl Bebop2 {
             gaz: 100,
             timestamp: Utc::now(),
             sequence_id,
-        }))));
-
-        let frame = Frame::for_drone(&self.drone, Type::Data, BufferID::CDNonAck, Some(feature));
-
-        self.drone.send_frame(frame)
+        })
     }
 
-    pub fn down(&self, sequence_id: u8) -> Result<(), Error> {
-        let feature = Feature::ArDrone3(Some(ArDrone3::Piloting(Piloting::PCMD(PCMD {
+    pub fn down(&self, sequence_id: u8) -> Result<AckHandle, Error> {
+        self.pcmd(PCMD {
             flag: true,
             roll: 0,
             pitch: 0,
@@ -66,14 +79,20 @@ impl Bebop2 {
             gaz: -100,
             timestamp: Utc::now(),
             sequence_id,
-        }))));
+        })
+    }
+
+    /// Sends the piloting command, it has to be re-sent periodically while flying
+    pub fn pcmd(&self, pcmd: PCMD) -> Result<AckHandle, Error> {
+        let feature = Feature::ArDrone3(Some(ArDrone3::Piloting(Piloting::PCMD(pcmd))));
 
         let frame = Frame::for_drone(&self.drone, Type::Data, BufferID::CDNonAck, Some(feature));
 
         self.drone.send_frame(frame)
     }
 
-    pub fn landing(&self) -> Result<(), Error> {
+    /// Wait on the returned `AckHandle` to know that the Drone accepted the command.
+    pub fn landing(&self) -> Result<AckHandle, Error> {
         let feature = Feature::ArDrone3(Some(ArDrone3::Piloting(Piloting::Landing)));
 
         let frame = Frame::for_drone(
@@ -85,4 +104,21 @@ impl Bebop2 {
 
         self.drone.send_frame(frame)
     }
+
+    /// Cuts the motors, the Drone falls!
+    ///
+    /// It's sent on the `BufferID::CDEmergency` buffer, which the Drone handles before the others.
+    /// Wait on the returned `AckHandle` to know that the Drone accepted the command.
+    pub fn emergency(&self) -> Result<AckHandle, Error> {
+        let feature = Feature::ArDrone3(Some(ArDrone3::Piloting(Piloting::Emergency)));
+
+        let frame = Frame::for_drone(
+            &self.drone,
+            Type::DataWithAck,
+            BufferID::CDEmergency,
+            Some(feature),
+        );
+
+        self.drone.send_frame(frame)
+    }
 }
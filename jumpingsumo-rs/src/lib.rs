@@ -3,7 +3,7 @@ use arsdk_rs::{
     frame::{BufferID, Frame, Type as FrameType},
     jumping_sumo::PilotState,
     jumping_sumo::{Anim, Class::*, PilotingID::*},
-    Config, ConnectionError, Drone, Error,
+    AckHandle, Config, ConnectionError, Drone, Error,
 };
 
 pub mod prelude {
@@ -25,7 +25,12 @@ impl JumpingSumo {
         })
     }
 
-    pub fn forward(&self) -> Result<(), Error> {
+    /// Stops the background threads of the `Drone`, see `Drone::disconnect()`
+    pub fn disconnect(&self) {
+        self.drone.disconnect()
+    }
+
+    pub fn forward(&self) -> Result<AckHandle, Error> {
         self.drive(PilotState {
             flag: true,
             speed: FORWARD_SPEED,
@@ -33,7 +38,7 @@ impl JumpingSumo {
         })
     }
 
-    pub fn backwards(&self) -> Result<(), Error> {
+    pub fn backwards(&self) -> Result<AckHandle, Error> {
         self.drive(PilotState {
             flag: true,
             speed: -FORWARD_SPEED,
@@ -41,7 +46,7 @@ impl JumpingSumo {
         })
     }
 
-    pub fn turn_left(&self) -> Result<(), Error> {
+    pub fn turn_left(&self) -> Result<AckHandle, Error> {
         self.drive(PilotState {
             flag: true,
             speed: 0,
@@ -49,7 +54,7 @@ impl JumpingSumo {
         })
     }
 
-    pub fn turn_right(&self) -> Result<(), Error> {
+    pub fn turn_right(&self) -> Result<AckHandle, Error> {
         self.drive(PilotState {
             flag: true,
             speed: 0,
@@ -57,7 +62,7 @@ impl JumpingSumo {
         })
     }
 
-    pub fn stop(&self) -> Result<(), Error> {
+    pub fn stop(&self) -> Result<AckHandle, Error> {
         self.drive(PilotState {
             flag: false,
             speed: 0,
@@ -65,7 +70,7 @@ impl JumpingSumo {
         })
     }
 
-    pub fn drive(&self, state: PilotState) -> Result<(), Error> {
+    pub fn drive(&self, state: PilotState) -> Result<AckHandle, Error> {
         let feature = JumpingSumoFeature(Piloting(Pilot(state)));
         let frame = Frame::for_drone(
             &self.drone,
@@ -77,7 +82,7 @@ impl JumpingSumo {
         self.drone.send_frame(frame)
     }
 
-    pub fn jump(&self) -> Result<(), Error> {
+    pub fn jump(&self) -> Result<AckHandle, Error> {
         let feature = JumpingSumoFeature(Animations(Anim::Jump));
         let frame = Frame::for_drone(
             &self.drone,
@@ -0,0 +1,27 @@
+use log::info;
+use mambo::prelude::*;
+use std::{error::Error, thread::sleep, time::Duration};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    env_logger::init();
+
+    // the Mambo FPV access point
+    let drone_ip: std::net::IpAddr = "192.168.99.3".parse()?;
+
+    let drone = Mambo::connect(drone_ip.into())?;
+
+    info!("Takeoff!");
+    drone.take_off()?.wait()?;
+
+    info!("Wait 5 seconds and flip");
+    sleep(Duration::from_secs(5));
+    drone.flip(FlipDirection::Back)?.wait()?;
+
+    info!("Wait 3 seconds and land");
+    sleep(Duration::from_secs(3));
+    drone.landing()?.wait()?;
+
+    drone.disconnect();
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use arsdk_rs::{
+    command::Feature,
+    frame::{BufferID, Frame, Type},
+    minidrone::{
+        animations::{self, Flip},
+        media_record, piloting,
+        usb_accessory::{self, ClawControl, GunControl},
+        Class,
+    },
+};
+
+pub use arsdk_rs::{
+    minidrone::{
+        animations::FlipDirection,
+        piloting::Pcmd,
+        usb_accessory::{ClawControlAction, GunControlAction},
+    },
+    prelude::*,
+};
+
+pub mod prelude {
+    pub use crate::Mambo;
+    pub use arsdk_rs::{
+        minidrone::{self, animations::FlipDirection, piloting::Pcmd},
+        prelude::*,
+    };
+}
+
+/// The Mambo (or the Swing) with the USB accessory (claw or gun) at id `0`
+pub struct Mambo {
+    drone: Drone,
+}
+
+impl Mambo {
+    pub fn connect(config: Config) -> Result<Self, ConnectionError> {
+        let drone = Drone::connect(config)?;
+
+        Ok(Self { drone })
+    }
+
+    /// Stops the background threads of the `Drone`, see `Drone::disconnect()`
+    pub fn disconnect(&self) {
+        self.drone.disconnect()
+    }
+
+    /// Wait on the returned `AckHandle` to know that the Drone accepted the command.
+    pub fn take_off(&self) -> Result<AckHandle, Error> {
+        self.send_with_ack(Class::Piloting(piloting::Command::TakeOff))
+    }
+
+    /// Wait on the returned `AckHandle` to know that the Drone accepted the command.
+    pub fn landing(&self) -> Result<AckHandle, Error> {
+        self.send_with_ack(Class::Piloting(piloting::Command::Landing))
+    }
+
+    /// Cuts the motors, the Drone falls!
+    ///
+    /// It's sent on the `BufferID::CDEmergency` buffer, which the Drone handles before the others.
+    /// Wait on the returned `AckHandle` to know that the Drone accepted the command.
+    pub fn emergency(&self) -> Result<AckHandle, Error> {
+        let feature = Feature::Minidrone(Some(Class::Piloting(piloting::Command::Emergency)));
+
+        let frame = Frame::for_drone(
+            &self.drone,
+            Type::DataWithAck,
+            BufferID::CDEmergency,
+            Some(feature),
+        );
+
+        self.drone.send_frame(frame)
+    }
+
+    pub fn up(&self, sequence_id: u8) -> Result<AckHandle, Error> {
+        self.pcmd(pcmd(0, 0, 0, 100, sequence_id))
+    }
+
+    pub fn down(&self, sequence_id: u8) -> Result<AckHandle, Error> {
+        self.pcmd(pcmd(0, 0, 0, -100, sequence_id))
+    }
+
+    /// Sends the piloting command, it has to be re-sent periodically while flying
+    pub fn pcmd(&self, pcmd: Pcmd) -> Result<AckHandle, Error> {
+        let feature = Feature::Minidrone(Some(Class::Piloting(piloting::Command::Pcmd(pcmd))));
+
+        let frame = Frame::for_drone(&self.drone, Type::Data, BufferID::CDNonAck, Some(feature));
+
+        self.drone.send_frame(frame)
+    }
+
+    pub fn flip(&self, direction: FlipDirection) -> Result<AckHandle, Error> {
+        self.send_with_ack(Class::Animations(animations::Command::Flip(Flip {
+            direction,
+        })))
+    }
+
+    pub fn open_claw(&self) -> Result<AckHandle, Error> {
+        self.send_with_ack(Class::UsbAccessory(usb_accessory::Command::ClawControl(
+            ClawControl {
+                id: 0,
+                action: ClawControlAction::Open,
+            },
+        )))
+    }
+
+    pub fn close_claw(&self) -> Result<AckHandle, Error> {
+        self.send_with_ack(Class::UsbAccessory(usb_accessory::Command::ClawControl(
+            ClawControl {
+                id: 0,
+                action: ClawControlAction::Close,
+            },
+        )))
+    }
+
+    pub fn fire_gun(&self) -> Result<AckHandle, Error> {
+        self.send_with_ack(Class::UsbAccessory(usb_accessory::Command::GunControl(
+            GunControl {
+                id: 0,
+                action: GunControlAction::Fire,
+            },
+        )))
+    }
+
+    /// Takes a picture with the bottom camera
+    pub fn take_picture(&self) -> Result<AckHandle, Error> {
+        self.send_with_ack(Class::MediaRecord(media_record::Command::PictureV2))
+    }
+
+    fn send_with_ack(&self, class: Class) -> Result<AckHandle, Error> {
+        let frame = Frame::for_drone(
+            &self.drone,
+            Type::DataWithAck,
+            BufferID::CDAck,
+            Some(Feature::Minidrone(Some(class))),
+        );
+
+        self.drone.send_frame(frame)
+    }
+}
+
+/// The `Pcmd` with the current timestamp in milliseconds (low 24 bits) and the sequence id (high 8 bits)
+pub fn pcmd(roll: i8, pitch: i8, yaw: i8, gaz: i8, sequence_id: u8) -> Pcmd {
+    let milliseconds = Utc::now().timestamp_millis() as u32 & 0x00ff_ffff;
+
+    Pcmd {
+        flag: (roll != 0 || pitch != 0).into(),
+        roll,
+        pitch,
+        yaw,
+        gaz,
+        timestamp: milliseconds | u32::from(sequence_id) << 24,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pcmd() {
+        let hover = pcmd(0, 0, 0, 50, 3);
+        assert_eq!(0, hover.flag);
+        assert_eq!(3, hover.timestamp >> 24);
+
+        assert_eq!(1, pcmd(0, -20, 0, 0, 0).flag);
+    }
+}
@@ -0,0 +1,17 @@
+use std::error::Error;
+
+use powerup_rs::prelude::*;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let drone_ip: std::net::IpAddr = "192.168.1.1".parse()?;
+
+    let power_up = PowerUp::connect(drone_ip.into())?;
+
+    power_up.buzz(true)?.wait()?;
+
+    std::thread::sleep(std::time::Duration::from_secs(3));
+
+    power_up.buzz(false)?.wait()?;
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use arsdk_rs::{
+    command::Feature::PowerUp as PowerUpFeature,
+    frame::{BufferID, Frame, Type as FrameType},
+    powerup::{
+        media_streaming::{self, VideoEnable},
+        piloting::{self, Pcmd, UserTakeOff},
+        piloting_settings::{self, Set},
+        sounds::{self, Buzz},
+        speed_settings::{self, MaxRotationSpeed, MaxVerticalSpeed},
+        Class,
+    },
+    AckHandle, Config, ConnectionError, Drone, Error,
+};
+
+pub use arsdk_rs::powerup::piloting_settings::SetSetting as PilotingSetting;
+
+pub mod prelude {
+    pub use crate::{PilotingSetting, PowerUp};
+    pub use arsdk_rs::prelude::*;
+}
+
+pub struct PowerUp {
+    drone: Drone,
+}
+
+impl PowerUp {
+    pub fn connect(config: Config) -> Result<Self, ConnectionError> {
+        Ok(Self {
+            drone: Drone::connect(config)?,
+        })
+    }
+
+    /// Stops the background threads of the `Drone`, see `Drone::disconnect()`
+    pub fn disconnect(&self) {
+        self.drone.disconnect()
+    }
+
+    /// Starts the motors once the PowerUp is thrown
+    pub fn user_take_off(&self) -> Result<AckHandle, Error> {
+        self.send(Class::Piloting(piloting::Command::UserTakeOff(
+            UserTakeOff { state: 1 },
+        )))
+    }
+
+    pub fn cancel_user_take_off(&self) -> Result<AckHandle, Error> {
+        self.send(Class::Piloting(piloting::Command::UserTakeOff(
+            UserTakeOff { state: 0 },
+        )))
+    }
+
+    /// `throttle` in `0..=100`, `roll` in `-100..=100`.
+    ///
+    /// It has to be re-sent periodically while flying.
+    pub fn fly(&self, throttle: u8, roll: i8) -> Result<AckHandle, Error> {
+        let pcmd = Pcmd {
+            flag: 1,
+            throttle,
+            roll,
+        };
+        let feature = PowerUpFeature(Some(Class::Piloting(piloting::Command::Pcmd(pcmd))));
+        let frame = Frame::for_drone(
+            &self.drone,
+            FrameType::Data,
+            BufferID::CDNonAck,
+            Some(feature),
+        );
+
+        self.drone.send_frame(frame)
+    }
+
+    pub fn set_piloting_setting(
+        &self,
+        setting: PilotingSetting,
+        value: f32,
+    ) -> Result<AckHandle, Error> {
+        self.send(Class::PilotingSettings(piloting_settings::Command::Set(
+            Set { setting, value },
+        )))
+    }
+
+    /// In m/s
+    pub fn set_max_vertical_speed(&self, current: f32) -> Result<AckHandle, Error> {
+        self.send(Class::SpeedSettings(
+            speed_settings::Command::MaxVerticalSpeed(MaxVerticalSpeed { current }),
+        ))
+    }
+
+    /// In degree/s
+    pub fn set_max_rotation_speed(&self, current: f32) -> Result<AckHandle, Error> {
+        self.send(Class::SpeedSettings(
+            speed_settings::Command::MaxRotationSpeed(MaxRotationSpeed { current }),
+        ))
+    }
+
+    pub fn enable_video(&self, enable: bool) -> Result<AckHandle, Error> {
+        self.send(Class::MediaStreaming(
+            media_streaming::Command::VideoEnable(VideoEnable {
+                enable: enable.into(),
+            }),
+        ))
+    }
+
+    pub fn buzz(&self, enable: bool) -> Result<AckHandle, Error> {
+        self.send(Class::Sounds(sounds::Command::Buzz(Buzz {
+            enable: enable.into(),
+        })))
+    }
+
+    fn send(&self, class: Class) -> Result<AckHandle, Error> {
+        let frame = Frame::for_drone(
+            &self.drone,
+            FrameType::DataWithAck,
+            BufferID::CDAck,
+            Some(PowerUpFeature(Some(class))),
+        );
+
+        self.drone.send_frame(frame)
+    }
+}